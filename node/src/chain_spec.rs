@@ -1,12 +1,17 @@
-use sp_core::{Pair, Public, sr25519};
+use std::{collections::BTreeSet, path::Path};
+use sp_core::{Pair, Public, ed25519, sr25519, crypto::Ss58Codec};
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	AccountId, AuraConfig, Balance, BalancesConfig, BlockNumber, CouncilMembershipConfig,
+	ExistentialDeposit, GenesisConfig, GrandpaConfig, IndicesConfig, SessionConfig,
+	SystemConfig, TechnicalMembershipConfig, TemplateModuleConfig, ValidatorSetConfig,
+	VestingConfig, WASM_BINARY, Signature, SS58Prefix, TOKEN_DECIMALS, TOKEN_SYMBOL,
+	opaque::SessionKeys,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{Verify, IdentifyAccount};
-use sc_service::ChainType;
+use sc_service::{ChainType, Properties, config::MultiaddrWithPeerId};
+use serde::Deserialize;
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
@@ -30,14 +35,103 @@ pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
+/// Generate an authority's account and its Aura and GRANDPA session keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
 	(
+		get_account_id_from_seed::<sr25519::Public>(s),
 		get_from_seed::<AuraId>(s),
 		get_from_seed::<GrandpaId>(s),
 	)
 }
 
+fn session_keys(aura: AuraId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { aura, grandpa }
+}
+
+/// Token and address format properties of the chain, as read by wallets.
+pub fn properties() -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("tokenSymbol".into(), TOKEN_SYMBOL.into());
+	properties.insert("tokenDecimals".into(), TOKEN_DECIMALS.into());
+	properties.insert("ss58Format".into(), SS58Prefix::get().into());
+	properties
+}
+
+/// Parse an account ID from its SS58 address.
+pub fn parse_account(s: &str) -> Result<AccountId, String> {
+	let public = sr25519::Public::from_ss58check(s)
+		.map_err(|e| format!("invalid account `{}`: {:?}", s, e))?;
+	Ok(AccountPublic::from(public).into_account())
+}
+
+/// A vesting schedule of an endowed account as `(account, begin, length, liquid)`. All but the
+/// `liquid` part of the account's balance is locked until block `begin`, and then unlocks linearly
+/// over `length` blocks.
+pub type VestingSchedule = (AccountId, BlockNumber, BlockNumber, Balance);
+
+/// Endowed accounts as read from a `generate-spec --endowments-file`.
+#[derive(Deserialize)]
+struct EndowmentsFile {
+	endowments: Vec<Endowment>,
+}
+
+#[derive(Deserialize)]
+struct Endowment {
+	account: String,
+	balance: Amount,
+	vesting: Option<Vesting>,
+}
+
+#[derive(Deserialize)]
+struct Vesting {
+	begin: BlockNumber,
+	length: BlockNumber,
+	liquid: Amount,
+}
+
+/// A balance in a file. Balances that do not fit in a TOML or JSON integer are given as strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Amount {
+	Number(u64),
+	Text(String),
+}
+
+/// Read endowed accounts, their balances and their vesting schedules from a TOML file (by `.toml`
+/// extension) or a JSON file.
+pub fn endowments_from_file(
+	path: &Path,
+) -> Result<(Vec<(AccountId, Balance)>, Vec<VestingSchedule>), String> {
+	let contents = std::fs::read_to_string(path)
+		.map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+	let file: EndowmentsFile = match path.extension().and_then(|e| e.to_str()) {
+		Some("toml") => toml::from_str(&contents).map_err(|e| e.to_string()),
+		_ => serde_json::from_str(&contents).map_err(|e| e.to_string()),
+	}.map_err(|e| format!("failed to parse {}: {}", path.display(), e))?;
+
+	let mut endowments = Vec::new();
+	let mut vesting = Vec::new();
+	for endowment in file.endowments {
+		let account = parse_account(&endowment.account)?;
+		endowments.push((account.clone(), endowment.balance.parse(&endowment.account)?));
+		if let Some(schedule) = endowment.vesting {
+			let liquid = schedule.liquid.parse(&endowment.account)?;
+			vesting.push((account, schedule.begin, schedule.length, liquid));
+		}
+	}
+	Ok((endowments, vesting))
+}
+
+impl Amount {
+	fn parse(self, account: &str) -> Result<Balance, String> {
+		match self {
+			Amount::Number(n) => Ok(n.into()),
+			Amount::Text(s) => s.parse()
+				.map_err(|e| format!("invalid balance `{}` for {}: {}", s, account, e)),
+		}
+	}
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
 
@@ -53,15 +147,17 @@ pub fn development_config() -> Result<ChainSpec, String> {
 			vec![
 				authority_keys_from_seed("Alice"),
 			],
-			// Sudo account
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
 			// Pre-funded accounts
 			vec![
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				get_account_id_from_seed::<sr25519::Public>("Bob"),
 				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			],
+			].into_iter().map(|k| (k, 1 << 60)).collect(),
+			// Vesting schedules
+			vec![],
+			// Initial value of the template pallet's `Something`
+			None,
 			true,
 		),
 		// Bootnodes
@@ -71,7 +167,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
-		None,
+		Some(properties()),
 		// Extensions
 		None,
 	))
@@ -93,8 +189,6 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				authority_keys_from_seed("Alice"),
 				authority_keys_from_seed("Bob"),
 			],
-			// Sudo account
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
 			// Pre-funded accounts
 			vec![
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -109,7 +203,11 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-			],
+			].into_iter().map(|k| (k, 1 << 60)).collect(),
+			// Vesting schedules
+			vec![],
+			// Initial value of the template pallet's `Something`
+			None,
 			true,
 		),
 		// Bootnodes
@@ -119,7 +217,90 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
+		Some(properties()),
+		// Extensions
 		None,
+	))
+}
+
+/// Chain spec for a network with the given authorities, endowed accounts and vesting schedules.
+#[allow(clippy::too_many_arguments)]
+pub fn custom_config(
+	name: &str,
+	id: &str,
+	chain_type: ChainType,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	endowed_accounts: Vec<(AccountId, Balance)>,
+	vesting: Vec<VestingSchedule>,
+	boot_nodes: Vec<MultiaddrWithPeerId>,
+	properties: Option<Properties>,
+) -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or("Wasm binary not available".to_string())?;
+
+	if initial_authorities.is_empty() {
+		return Err("at least one authority is required".into());
+	}
+	// An authority's account is derived from its Aura key, so the accounts are unique exactly
+	// when the Aura keys are. A GRANDPA key given to two authorities makes the genesis build panic.
+	let (mut accounts, mut grandpa_keys) = (BTreeSet::new(), BTreeSet::new());
+	for (account, _, grandpa) in &initial_authorities {
+		if !accounts.insert(account) {
+			return Err(format!("authority {} is given more than once", account));
+		}
+		if !grandpa_keys.insert(grandpa) {
+			return Err(format!(
+				"GRANDPA key {} is given to more than one authority",
+				ed25519::Public::from(grandpa.clone()).to_ss58check(),
+			));
+		}
+	}
+	let mut seen = BTreeSet::new();
+	for (account, balance) in &endowed_accounts {
+		if !seen.insert(account) {
+			return Err(format!("account {} is endowed more than once", account));
+		}
+		// Balances below the existential deposit make the genesis build panic.
+		if *balance < ExistentialDeposit::get() {
+			return Err(format!(
+				"balance {} of {} is below the existential deposit of {}",
+				balance, account, ExistentialDeposit::get(),
+			));
+		}
+	}
+	let mut seen = BTreeSet::new();
+	for (account, _, _, liquid) in &vesting {
+		if !seen.insert(account) {
+			return Err(format!("account {} has more than one vesting schedule", account));
+		}
+		// Vesting an account without a balance makes the genesis build panic.
+		match endowed_accounts.iter().find(|(endowed, _)| endowed == account) {
+			None => return Err(format!("vesting account {} is not endowed", account)),
+			Some((_, balance)) if liquid >= balance => return Err(format!(
+				"liquid balance {} of {} leaves nothing of its endowment {} to vest",
+				liquid, account, balance,
+			)),
+			Some(_) => {},
+		}
+	}
+
+	Ok(ChainSpec::from_genesis(
+		name,
+		id,
+		chain_type,
+		move || testnet_genesis(
+			wasm_binary,
+			initial_authorities.clone(),
+			endowed_accounts.clone(),
+			vesting.clone(),
+			None,
+			true,
+		),
+		boot_nodes,
+		// Telemetry
+		None,
+		// Protocol ID
+		None,
+		properties,
 		// Extensions
 		None,
 	))
@@ -128,9 +309,10 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
-	root_key: AccountId,
-	endowed_accounts: Vec<AccountId>,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	endowed_accounts: Vec<(AccountId, Balance)>,
+	vesting: Vec<VestingSchedule>,
+	initial_something: Option<u64>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -140,18 +322,50 @@ fn testnet_genesis(
 			changes_trie_config: Default::default(),
 		}),
 		pallet_balances: Some(BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k|(k, 1 << 60)).collect(),
+			// Configure endowed accounts with their initial balances.
+			balances: endowed_accounts,
 		}),
+		pallet_validator_set: Some(ValidatorSetConfig {
+			validators: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+		}),
+		pallet_session: Some(SessionConfig {
+			keys: initial_authorities.iter().map(|x| (
+				x.0.clone(),
+				x.0.clone(),
+				session_keys(x.1.clone(), x.2.clone()),
+			)).collect(),
+		}),
+		// Aura and GRANDPA get their authorities from the session pallet.
 		pallet_aura: Some(AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+			authorities: vec![],
 		}),
 		pallet_grandpa: Some(GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+			authorities: vec![],
+		}),
+		pallet_template: Some(TemplateModuleConfig {
+			something: initial_something,
+			something_of: vec![],
+		}),
+		// Creates the treasury account, which collects fees and dust.
+		pallet_treasury: Some(Default::default()),
+		// The authorities make up the first council and technical committee. Their seats are
+		// managed by the membership pallets.
+		pallet_collective_Instance1: Some(Default::default()),
+		pallet_membership_Instance1: Some(CouncilMembershipConfig {
+			members: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+			phantom: Default::default(),
+		}),
+		pallet_collective_Instance2: Some(Default::default()),
+		pallet_membership_Instance2: Some(TechnicalMembershipConfig {
+			members: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+			phantom: Default::default(),
+		}),
+		pallet_democracy: Some(Default::default()),
+		pallet_indices: Some(IndicesConfig {
+			indices: vec![],
 		}),
-		pallet_sudo: Some(SudoConfig {
-			// Assign network admin rights.
-			key: root_key,
+		pallet_vesting: Some(VestingConfig {
+			vesting,
 		}),
 	}
 }
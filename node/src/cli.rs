@@ -1,5 +1,12 @@
+use std::path::PathBuf;
 use structopt::StructOpt;
-use sc_cli::RunCmd;
+use sc_cli::{CliConfiguration, KeystoreParams, RunCmd, SharedParams};
+use sc_service::{ChainType, Configuration, config::{KeystoreConfig, MultiaddrWithPeerId}};
+use sp_core::{Pair, crypto::{key_types, Ss58Codec}, ed25519, sr25519};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+use node_template_runtime::{AccountId, Balance, BlockNumber};
+use crate::chain_spec;
 
 #[derive(Debug, StructOpt)]
 pub struct Cli {
@@ -8,10 +15,46 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// How blocks are authored: `aura` for the normal slot-based consensus, `instant` to author
+	/// and finalize a block for every transaction, or `manual` to author and finalize blocks only
+	/// on `engine_createBlock` and `engine_finalizeBlock` RPC calls, which `instant` takes too.
+	#[structopt(long, default_value = "aura")]
+	pub sealing: Sealing,
+}
+
+/// Block authoring modes supported by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sealing {
+	/// Author blocks in Aura slots and finalize them with GRANDPA.
+	Aura,
+	/// Author and finalize a block as soon as a transaction enters the pool.
+	Instant,
+	/// Author and finalize blocks when asked to over RPC.
+	Manual,
+}
+
+impl std::str::FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"aura" => Ok(Sealing::Aura),
+			"instant" => Ok(Sealing::Instant),
+			"manual" => Ok(Sealing::Manual),
+			_ => Err(format!("unknown sealing mode `{}`; expected aura, instant or manual", s)),
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
 pub enum Subcommand {
+	/// Key management cli utilities
+	Key(sc_cli::KeySubcommand),
+
+	/// Insert Aura and GRANDPA keys derived from one secret into the node's keystore.
+	InsertSessionKeys(InsertSessionKeysCmd),
+
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
@@ -33,7 +76,223 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Generate a chain specification with custom authorities and endowed accounts.
+	GenerateSpec(GenerateSpecCmd),
+
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 }
+
+/// The `generate-spec` command.
+///
+/// Keys and accounts are given as SS58 addresses.
+#[derive(Debug, StructOpt)]
+pub struct GenerateSpecCmd {
+	/// Name of the chain.
+	#[structopt(long, default_value = "Custom Testnet")]
+	pub name: String,
+
+	/// Identifier of the chain, also used as its database directory name.
+	#[structopt(long, default_value = "custom_testnet")]
+	pub id: String,
+
+	/// Type of the chain: `development`, `local` or `live`.
+	#[structopt(long, default_value = "live", parse(try_from_str = parse_chain_type))]
+	pub chain_type: ChainType,
+
+	/// An initial authority as `<aura-sr25519-key>,<grandpa-ed25519-key>`. Its validator account
+	/// is the account of the Aura key.
+	///
+	/// Pass once per authority.
+	#[structopt(long = "authority", required = true, parse(try_from_str = parse_authority))]
+	pub authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+
+	/// An endowed account as `<account>=<balance>`.
+	///
+	/// Pass once per account.
+	#[structopt(long = "endow", parse(try_from_str = parse_endowment))]
+	pub endowments: Vec<(AccountId, Balance)>,
+
+	/// A TOML or JSON file listing further endowed accounts, as an `endowments` array of
+	/// `{ account, balance }` entries with an optional `vesting = { begin, length, liquid }`.
+	#[structopt(long, parse(from_os_str))]
+	pub endowments_file: Option<PathBuf>,
+
+	/// A vesting schedule of an endowed account as `<account>=<begin>,<length>,<liquid>`. All but
+	/// `liquid` of the account's endowment unlocks linearly over `length` blocks from `begin`.
+	///
+	/// Pass once per account.
+	#[structopt(long = "vest", parse(try_from_str = parse_vesting))]
+	pub vesting: Vec<chain_spec::VestingSchedule>,
+
+	/// A boot node address, including its peer ID.
+	#[structopt(long = "bootnode")]
+	pub bootnodes: Vec<MultiaddrWithPeerId>,
+
+	/// Symbol of the native token, shown by wallets. Defaults to the runtime's.
+	#[structopt(long)]
+	pub token_symbol: Option<String>,
+
+	/// Number of decimals of the native token, shown by wallets. Defaults to the runtime's.
+	#[structopt(long)]
+	pub token_decimals: Option<u32>,
+
+	/// SS58 address format that wallets should use for this chain. Defaults to the runtime's.
+	#[structopt(long)]
+	pub ss58_format: Option<u8>,
+
+	/// Output the raw storage of the genesis block instead of the human-readable genesis config.
+	#[structopt(long)]
+	pub raw: bool,
+}
+
+impl GenerateSpecCmd {
+	/// Build the chain spec and print it to stdout.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let mut endowments = self.endowments.clone();
+		let mut vesting = self.vesting.clone();
+		if let Some(path) = &self.endowments_file {
+			let (file_endowments, file_vesting) = chain_spec::endowments_from_file(path)?;
+			endowments.extend(file_endowments);
+			vesting.extend(file_vesting);
+		}
+
+		let mut properties = chain_spec::properties();
+		if let Some(symbol) = &self.token_symbol {
+			properties.insert("tokenSymbol".into(), symbol.clone().into());
+		}
+		if let Some(decimals) = self.token_decimals {
+			properties.insert("tokenDecimals".into(), decimals.into());
+		}
+		if let Some(format) = self.ss58_format {
+			properties.insert("ss58Format".into(), format.into());
+		}
+
+		let spec = chain_spec::custom_config(
+			&self.name,
+			&self.id,
+			self.chain_type.clone(),
+			self.authorities.clone(),
+			endowments,
+			vesting,
+			self.bootnodes.clone(),
+			Some(properties),
+		)?;
+		println!("{}", spec.as_json(self.raw)?);
+
+		Ok(())
+	}
+}
+
+/// The `insert-session-keys` command.
+#[derive(Debug, StructOpt)]
+pub struct InsertSessionKeysCmd {
+	/// Secret URI to derive the keys from, e.g. `//Alice` for the keys of
+	/// `authority_keys_from_seed("Alice")`. A new secret phrase is generated when omitted.
+	#[structopt(long)]
+	pub suri: Option<String>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl InsertSessionKeysCmd {
+	/// Insert the keys into the keystore of `config` and print their public keys.
+	pub fn run(&self, config: Configuration) -> sc_cli::Result<()> {
+		let (path, password) = match config.keystore {
+			KeystoreConfig::Path { path, password } => (path, password),
+			KeystoreConfig::InMemory => return Err("The keystore is not on disk.".into()),
+		};
+
+		let suri = match &self.suri {
+			Some(suri) => suri.clone(),
+			None => {
+				let (_, phrase, _) = sr25519::Pair::generate_with_phrase(None);
+				println!("Secret phrase (keep it safe): {}", phrase);
+				phrase
+			},
+		};
+		let aura = sr25519::Pair::from_string(&suri, None)
+			.map_err(|e| format!("Invalid secret URI for the Aura key: {:?}", e))?
+			.public();
+		let grandpa = ed25519::Pair::from_string(&suri, None)
+			.map_err(|e| format!("Invalid secret URI for the GRANDPA key: {:?}", e))?
+			.public();
+
+		let keystore = sc_keystore::Store::open(path, password)
+			.map_err(|e| format!("Failed to open the keystore: {}", e))?;
+		let mut keystore = keystore.write();
+		keystore.insert_unknown(key_types::AURA, &suri, aura.as_ref())
+			.map_err(|_| "Failed to insert the Aura key.")?;
+		keystore.insert_unknown(key_types::GRANDPA, &suri, grandpa.as_ref())
+			.map_err(|_| "Failed to insert the GRANDPA key.")?;
+
+		println!("Aura (sr25519):    {}", aura.to_ss58check());
+		println!("GRANDPA (ed25519): {}", grandpa.to_ss58check());
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for InsertSessionKeysCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+}
+
+fn parse_chain_type(s: &str) -> Result<ChainType, String> {
+	match s {
+		"dev" | "development" => Ok(ChainType::Development),
+		"local" => Ok(ChainType::Local),
+		"live" => Ok(ChainType::Live),
+		_ => Err(format!("unknown chain type `{}`; expected development, local or live", s)),
+	}
+}
+
+fn parse_authority(s: &str) -> Result<(AccountId, AuraId, GrandpaId), String> {
+	let (aura, grandpa) = split_pair(s, ',')?;
+	let account = chain_spec::parse_account(aura)?;
+	let aura = sr25519::Public::from_ss58check(aura)
+		.map_err(|e| format!("invalid Aura key `{}`: {:?}", aura, e))?;
+	let grandpa = ed25519::Public::from_ss58check(grandpa)
+		.map_err(|e| format!("invalid GRANDPA key `{}`: {:?}", grandpa, e))?;
+	Ok((account, aura.into(), grandpa.into()))
+}
+
+fn parse_endowment(s: &str) -> Result<(AccountId, Balance), String> {
+	let (account, balance) = split_pair(s, '=')?;
+	let balance = balance.parse()
+		.map_err(|e| format!("invalid balance `{}`: {}", balance, e))?;
+	Ok((chain_spec::parse_account(account)?, balance))
+}
+
+fn parse_vesting(s: &str) -> Result<chain_spec::VestingSchedule, String> {
+	let (account, schedule) = split_pair(s, '=')?;
+	let (begin, rest) = split_pair(schedule, ',')?;
+	let (length, liquid) = split_pair(rest, ',')?;
+	let begin: BlockNumber = begin.parse()
+		.map_err(|e| format!("invalid begin block `{}`: {}", begin, e))?;
+	let length: BlockNumber = length.parse()
+		.map_err(|e| format!("invalid length `{}`: {}", length, e))?;
+	let liquid: Balance = liquid.parse()
+		.map_err(|e| format!("invalid liquid balance `{}`: {}", liquid, e))?;
+	Ok((chain_spec::parse_account(account)?, begin, length, liquid))
+}
+
+fn split_pair(s: &str, separator: char) -> Result<(&str, &str), String> {
+	let mut parts = s.splitn(2, separator);
+	match (parts.next(), parts.next()) {
+		(Some(first), Some(second)) => Ok((first.trim(), second.trim())),
+		_ => Err(format!("expected `<first>{}<second>`, got `{}`", separator, s)),
+	}
+}
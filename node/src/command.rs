@@ -16,10 +16,12 @@
 // limitations under the License.
 
 use crate::{chain_spec, service};
-use crate::cli::{Cli, Subcommand};
+use crate::cli::{Cli, Sealing, Subcommand};
 use sc_cli::{SubstrateCli, RuntimeVersion, Role, ChainSpec};
 use sc_service::PartialComponents;
-use node_template_runtime::Block;
+use node_template_runtime::{Block, SS58Prefix};
+use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat};
+use std::convert::TryFrom;
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -63,9 +65,21 @@ impl SubstrateCli for Cli {
 
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
+	// Print and parse addresses in the chain's own format. This must happen before the arguments
+	// are parsed, as addresses given in a custom format are rejected unless it is the default.
+	let prefix = SS58Prefix::get();
+	set_default_ss58_version(
+		Ss58AddressFormat::try_from(prefix).unwrap_or(Ss58AddressFormat::Custom(prefix)),
+	);
+
 	let cli = Cli::from_args();
 
 	match &cli.subcommand {
+		Some(Subcommand::Key(cmd)) => cmd.run(),
+		Some(Subcommand::InsertSessionKeys(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(config))
+		},
 		Some(Subcommand::BuildSpec(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
@@ -114,6 +128,7 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend), task_manager))
 			})
 		},
+		Some(Subcommand::GenerateSpec(cmd)) => cmd.run(),
 		Some(Subcommand::Benchmark(cmd)) => {
 			if cfg!(feature = "runtime-benchmarks") {
 				let runner = cli.create_runner(cmd)?;
@@ -126,9 +141,11 @@ pub fn run() -> sc_cli::Result<()> {
 		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
-			runner.run_node_until_exit(|config| match config.role {
-				Role::Light => service::new_light(config),
-				_ => service::new_full(config),
+			let sealing = cli.sealing;
+			runner.run_node_until_exit(|config| match (config.role.clone(), sealing) {
+				(Role::Light, _) => service::new_light(config),
+				(_, Sealing::Aura) => service::new_full(config),
+				(_, sealing) => service::new_manual_seal(config, sealing),
 			})
 		}
 	}
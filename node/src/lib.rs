@@ -1,3 +1,4 @@
 pub mod chain_spec;
 pub mod service;
 pub mod rpc;
+pub mod cli;
@@ -7,7 +7,9 @@
 
 use std::sync::Arc;
 
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index};
+use futures::channel::mpsc::Sender;
+use node_template_runtime::{opaque::Block, AccountId, AssetId, Balance, Hash, Index};
+use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApi};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Error as BlockChainError, HeaderMetadata, HeaderBackend};
 use sp_block_builder::BlockBuilder;
@@ -23,6 +25,8 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Channel for sending commands to manual seal, if the node authors blocks on request.
+	pub command_sink: Option<Sender<EngineCommand<Hash>>>,
 }
 
 /// Instantiate all full RPC extensions.
@@ -34,17 +38,22 @@ pub fn create_full<C, P>(
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_template_rpc::TemplateRuntimeApi<Block, AccountId>,
+	C::Api: pallet_assets_rpc::AssetsRuntimeApi<Block, AccountId, AssetId, Balance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use pallet_template_rpc::{Template, TemplateApi};
+	use pallet_assets_rpc::{Assets, AssetsApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps {
 		client,
 		pool,
 		deny_unsafe,
+		command_sink,
 	} = deps;
 
 	io.extend_with(
@@ -55,6 +64,22 @@ pub fn create_full<C, P>(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
 
+	io.extend_with(
+		TemplateApi::to_delegate(Template::new(client.clone()))
+	);
+
+	io.extend_with(
+		AssetsApi::to_delegate(Assets::new(client.clone()))
+	);
+
+	if let Some(command_sink) = command_sink {
+		io.extend_with(
+			// The RPC handler gets the sending end of the channel so that it can pass
+			// `EngineCommand`s to the background block authorship task.
+			ManualSealApi::to_delegate(ManualSeal::new(command_sink))
+		);
+	}
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -1,11 +1,17 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sc_client_api::{ExecutorProvider, RemoteBackend};
-use node_template_runtime::{self, opaque::Block, RuntimeApi};
+use node_template_runtime::{self, opaque::Block, RuntimeApi, SLOT_DURATION};
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
-use sp_inherents::InherentDataProviders;
+use sp_inherents::{
+	InherentData, InherentDataProviders, InherentIdentifier, ProvideInherentData,
+};
+use crate::cli::Sealing;
+use futures::StreamExt;
+use sc_consensus_manual_seal::rpc::EngineCommand;
 use sc_executor::native_executor_instance;
 pub use sc_executor::NativeExecutor;
 use sp_consensus_aura::sr25519::{AuthorityPair as AuraPair};
@@ -125,6 +131,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 				client: client.clone(),
 				pool: pool.clone(),
 				deny_unsafe,
+				command_sink: None,
 			};
 
 			crate::rpc::create_full(deps)
@@ -226,6 +233,176 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	Ok(task_manager)
 }
 
+/// Provides timestamps that advance by exactly one slot per authored block.
+///
+/// The runtime's Aura pallet rejects blocks whose timestamps fall in the same slot, which blocks
+/// authored on demand easily do. Timestamps start at the current time, so a chain authored faster
+/// than one block per slot runs ahead of the wall clock and cannot be resumed after a restart;
+/// use `--tmp` or purge the chain between runs.
+struct SlotTimestampProvider {
+	next: AtomicU64,
+}
+
+impl SlotTimestampProvider {
+	fn new() -> Self {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH)
+			.expect("Current time is always after unix epoch; qed")
+			.as_millis() as u64;
+		Self { next: AtomicU64::new(now) }
+	}
+}
+
+impl ProvideInherentData for SlotTimestampProvider {
+	fn inherent_identifier(&self) -> &'static InherentIdentifier {
+		&sp_timestamp::INHERENT_IDENTIFIER
+	}
+
+	fn provide_inherent_data(
+		&self,
+		inherent_data: &mut InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		let timestamp: sp_timestamp::InherentType =
+			self.next.fetch_add(SLOT_DURATION, Ordering::SeqCst);
+		inherent_data.put_data(sp_timestamp::INHERENT_IDENTIFIER, &timestamp)
+	}
+
+	fn error_to_string(&self, error: &[u8]) -> Option<String> {
+		sp_timestamp::InherentError::try_from(&sp_timestamp::INHERENT_IDENTIFIER, error)
+			.map(|e| format!("{:?}", e))
+	}
+}
+
+/// Builds a new service for a full client that authors blocks with manual seal instead of Aura,
+/// either for every transaction (`Sealing::Instant`) or on RPC request (`Sealing::Manual`).
+/// GRANDPA does not run: instant seal finalizes the blocks it authors, and in both modes blocks
+/// can be created and finalized through `engine_createBlock` and `engine_finalizeBlock`.
+pub fn new_manual_seal(
+	config: Configuration,
+	sealing: Sealing,
+) -> Result<TaskManager, ServiceError> {
+	let (client, backend, keystore, mut task_manager) =
+		sc_service::new_full_parts::<Block, RuntimeApi, Executor>(&config)?;
+	let client = Arc::new(client);
+
+	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+
+	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+		config.transaction_pool.clone(),
+		config.prometheus_registry(),
+		task_manager.spawn_handle(),
+		client.clone(),
+	);
+
+	let import_queue = sc_consensus_manual_seal::import_queue(
+		Box::new(client.clone()),
+		&task_manager.spawn_handle(),
+		config.prometheus_registry(),
+	);
+
+	let (network, network_status_sinks, system_rpc_tx, network_starter) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue,
+			on_demand: None,
+			block_announce_validator_builder: None,
+			finality_proof_request_builder: None,
+			finality_proof_provider: None,
+		})?;
+
+	if config.offchain_worker.enabled {
+		sc_service::build_offchain_workers(
+			&config, backend.clone(), task_manager.spawn_handle(), client.clone(), network.clone(),
+		);
+	}
+
+	let prometheus_registry = config.prometheus_registry().cloned();
+
+	// Both modes take commands sent through the RPC, so blocks can always be created and
+	// finalized by hand.
+	let (command_sink, rpc_commands) = futures::channel::mpsc::channel(1024);
+
+	let rpc_extensions_builder = {
+		let client = client.clone();
+		let pool = transaction_pool.clone();
+
+		Box::new(move |deny_unsafe, _| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: Some(command_sink.clone()),
+			};
+
+			crate::rpc::create_full(deps)
+		})
+	};
+
+	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		network: network.clone(),
+		client: client.clone(),
+		keystore,
+		task_manager: &mut task_manager,
+		transaction_pool: transaction_pool.clone(),
+		telemetry_connection_sinks: sc_service::TelemetryConnectionSinks::default(),
+		rpc_extensions_builder,
+		on_demand: None,
+		remote_blockchain: None,
+		backend, network_status_sinks, system_rpc_tx, config,
+	})?;
+
+	let inherent_data_providers = InherentDataProviders::new();
+	inherent_data_providers
+		.register_provider(SlotTimestampProvider::new())
+		.map_err(|e| ServiceError::Other(format!("{:?}", e)))?;
+
+	let proposer = sc_basic_authorship::ProposerFactory::new(
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry.as_ref(),
+	);
+
+	// Instant seal also authors, and finalizes, a block whenever a transaction is imported into
+	// the pool. Neither mode runs a finality gadget, so blocks are finalized as they are sealed
+	// unless an RPC command says otherwise.
+	let commands_stream = match sealing {
+		Sealing::Instant => {
+			let pool_commands = transaction_pool.pool().validated_pool()
+				.import_notification_stream()
+				.map(|_| EngineCommand::SealNewBlock {
+					create_empty: false,
+					finalize: true,
+					parent_hash: None,
+					sender: None,
+				});
+			futures::stream::select(rpc_commands, pool_commands).boxed()
+		},
+		_ => rpc_commands.boxed(),
+	};
+
+	let authorship_future = sc_consensus_manual_seal::run_manual_seal(
+		sc_consensus_manual_seal::ManualSealParams {
+			block_import: client.clone(),
+			env: proposer,
+			client,
+			pool: transaction_pool.pool().clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			inherent_data_providers,
+		}
+	);
+
+	// the manual seal authoring task is considered essential, i.e. if it
+	// fails we take down the service with it.
+	task_manager.spawn_essential_handle().spawn_blocking("manual-seal", authorship_future);
+
+	network_starter.start_network();
+	Ok(task_manager)
+}
+
 /// Builds a new service for a light client.
 pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let (client, backend, keystore, mut task_manager, on_demand) =
@@ -0,0 +1,18 @@
+//! Runtime API definition for the assets pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	/// Read the assets pallet's state without computing storage keys by hand.
+	pub trait AssetsApi<AccountId, AssetId, Balance> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// Every asset `who` holds, with the amount it holds.
+		fn account_balances(who: AccountId) -> Vec<(AssetId, Balance)>;
+	}
+}
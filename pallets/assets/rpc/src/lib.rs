@@ -0,0 +1,97 @@
+//! RPC interface for the assets pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::U256;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_assets_rpc_runtime_api::AssetsApi as AssetsRuntimeApi;
+
+/// Assets pallet RPC methods.
+#[rpc]
+pub trait AssetsApi<BlockHash, AccountId, AssetId, Balance> {
+	/// Every asset `who` holds as `[asset_id, balance]` pairs, in no particular order, at the
+	/// given block or the best block.
+	///
+	/// Balances that fit in a `u64` are JSON numbers and larger ones are hex strings, since
+	/// JavaScript numbers cannot represent every `u128`.
+	#[rpc(name = "assets_accountBalances")]
+	fn account_balances(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(AssetId, NumberOrHex)>>;
+}
+
+/// Implements the [`AssetsApi`] RPC trait for interacting with the assets pallet.
+pub struct Assets<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Assets<C, B> {
+	/// Create new `Assets` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, AssetId, Balance>
+	AssetsApi<<Block as BlockT>::Hash, AccountId, AssetId, Balance> for Assets<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AssetsRuntimeApi<Block, AccountId, AssetId, Balance>,
+	AccountId: Codec,
+	AssetId: Codec,
+	Balance: Codec + Into<U256>,
+{
+	fn account_balances(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AssetId, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let balances = api.account_balances(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query the balances of the account.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		Ok(balances.into_iter().map(|(id, balance)| (id, number_or_hex(balance.into()))).collect())
+	}
+}
+
+/// `value` as a JSON number if it fits in a `u64`, and as a hex string otherwise.
+fn number_or_hex(value: U256) -> NumberOrHex {
+	if value <= U256::from(u64::max_value()) {
+		NumberOrHex::Number(value.low_u64())
+	} else {
+		NumberOrHex::Hex(value)
+	}
+}
@@ -0,0 +1,154 @@
+//! Benchmarking setup for pallet-assets
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup};
+
+const SEED: u32 = 0;
+
+// Give `who` enough free balance to pay the deposits of an asset and its metadata.
+fn fund<T: Trait>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, DepositBalanceOf::<T>::max_value() / 2u32.into());
+}
+
+// Have the whitelisted caller create asset `Default::default()` with a minimum balance of one,
+// and mint `supply` of it to itself.
+fn create_default_asset<T: Trait>(supply: u32) -> (T::AccountId, T::AssetId) {
+	let caller: T::AccountId = whitelisted_caller();
+	fund::<T>(&caller);
+	let id = T::AssetId::default();
+	let origin: T::Origin = RawOrigin::Signed(caller.clone()).into();
+	Module::<T>::create(origin.clone(), id, 1u32.into()).unwrap();
+	if supply > 0 {
+		let source = T::Lookup::unlookup(caller.clone());
+		Module::<T>::mint(origin, id, source, supply.into()).unwrap();
+	}
+	(caller, id)
+}
+
+benchmarks! {
+	_ { }
+
+	create {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let id = T::AssetId::default();
+	}: _(RawOrigin::Signed(caller.clone()), id, 1u32.into())
+	verify {
+		assert_eq!(Module::<T>::asset(id).map(|details| details.owner), Some(caller));
+	}
+
+	destroy {
+		let (caller, id) = create_default_asset::<T>(0);
+		let origin = RawOrigin::Signed(caller.clone()).into();
+		Module::<T>::set_metadata(origin, id, vec![0; 4], vec![0; 4], 12)?;
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert_eq!(Module::<T>::asset(id), None);
+	}
+
+	mint {
+		let (caller, id) = create_default_asset::<T>(0);
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let target = T::Lookup::unlookup(beneficiary.clone());
+	}: _(RawOrigin::Signed(caller), id, target, 100u32.into())
+	verify {
+		assert_eq!(Module::<T>::balance(id, beneficiary), 100u32.into());
+	}
+
+	burn {
+		let (caller, id) = create_default_asset::<T>(100);
+		let source = T::Lookup::unlookup(caller.clone());
+	}: _(RawOrigin::Signed(caller.clone()), id, source, 100u32.into())
+	verify {
+		assert_eq!(Module::<T>::balance(id, caller), 0u32.into());
+	}
+
+	transfer {
+		let (caller, id) = create_default_asset::<T>(100);
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(RawOrigin::Signed(caller.clone()), id, target_lookup, 50u32.into())
+	verify {
+		assert_eq!(Module::<T>::balance(id, caller), 50u32.into());
+		assert_eq!(Module::<T>::balance(id, target), 50u32.into());
+	}
+
+	freeze {
+		let (caller, id) = create_default_asset::<T>(100);
+		let who = T::Lookup::unlookup(caller.clone());
+	}: _(RawOrigin::Signed(caller.clone()), id, who)
+	verify {
+		assert_eq!(Module::<T>::account(&caller, id).map(|a| a.is_frozen), Some(true));
+	}
+
+	thaw {
+		let (caller, id) = create_default_asset::<T>(100);
+		let who = T::Lookup::unlookup(caller.clone());
+		Module::<T>::freeze(RawOrigin::Signed(caller.clone()).into(), id, who.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), id, who)
+	verify {
+		assert_eq!(Module::<T>::account(&caller, id).map(|a| a.is_frozen), Some(false));
+	}
+
+	freeze_asset {
+		let (caller, id) = create_default_asset::<T>(0);
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert_eq!(Module::<T>::asset(id).map(|details| details.is_frozen), Some(true));
+	}
+
+	thaw_asset {
+		let (caller, id) = create_default_asset::<T>(0);
+		Module::<T>::freeze_asset(RawOrigin::Signed(caller.clone()).into(), id)?;
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert_eq!(Module::<T>::asset(id).map(|details| details.is_frozen), Some(false));
+	}
+
+	set_metadata {
+		let n in 0 .. T::StringLimit::get();
+		let s in 0 .. T::StringLimit::get();
+		let (caller, id) = create_default_asset::<T>(0);
+		let name = vec![0u8; n as usize];
+		let symbol = vec![0u8; s as usize];
+	}: _(RawOrigin::Signed(caller), id, name.clone(), symbol, 12)
+	verify {
+		assert_eq!(Module::<T>::metadata(id).map(|metadata| metadata.name), Some(name));
+	}
+
+	clear_metadata {
+		let (caller, id) = create_default_asset::<T>(0);
+		let origin = RawOrigin::Signed(caller.clone()).into();
+		Module::<T>::set_metadata(origin, id, vec![0; 4], vec![0; 4], 12)?;
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert_eq!(Module::<T>::metadata(id), None);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create::<Test>());
+			assert_ok!(test_benchmark_destroy::<Test>());
+			assert_ok!(test_benchmark_mint::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_freeze::<Test>());
+			assert_ok!(test_benchmark_thaw::<Test>());
+			assert_ok!(test_benchmark_freeze_asset::<Test>());
+			assert_ok!(test_benchmark_thaw_asset::<Test>());
+			assert_ok!(test_benchmark_set_metadata::<Test>());
+			assert_ok!(test_benchmark_clear_metadata::<Test>());
+		});
+	}
+}
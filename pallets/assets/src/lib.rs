@@ -0,0 +1,558 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Assets Pallet
+//!
+//! Fungible assets that any account can issue alongside the native currency.
+//!
+//! An account creates an asset under an unused id with `create`, which reserves `T::AssetDeposit`
+//! of the native currency from it until the asset is destroyed. The creator owns the asset: it
+//! mints and burns its supply, freezes and thaws it as a whole or per account, and sets its name,
+//! symbol and number of decimals. Metadata reserves a further deposit of `T::MetadataDepositBase`
+//! plus `T::MetadataDepositPerByte` for each byte of the name and symbol. An asset can only be
+//! destroyed once its whole supply has been burned, which returns both deposits to the owner.
+//!
+//! Holders move their balances with `transfer`, unless the asset or their account is frozen.
+//! Each asset has a minimum balance: no account may hold less than it and more than nothing, so a
+//! transfer that would leave less than the minimum behind moves the remainder along with it.
+//!
+//! At most `T::MaxHolders` accounts hold an asset at once. Holdings are not paid for by their
+//! holders, so `T::AssetDeposit` should cover the storage of that many of them.
+
+use codec::{Encode, Decode};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, Parameter,
+	IterableStorageDoubleMap,
+	traits::{Currency, Get, ReservableCurrency},
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+	DispatchError,
+	traits::{AtLeast32BitUnsigned, CheckedAdd, Member, Saturating, StaticLookup, Zero},
+};
+use sp_std::prelude::*;
+
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub use weights::WeightInfo;
+
+type DepositBalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type AssetDetailsOf<T> = AssetDetails<
+	<T as Trait>::Balance,
+	<T as frame_system::Trait>::AccountId,
+	DepositBalanceOf<T>,
+>;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The type of asset balances.
+	type Balance: Member + Parameter + AtLeast32BitUnsigned + Default + Copy;
+
+	/// The type of asset ids.
+	type AssetId: Member + Parameter + Default + Copy;
+
+	/// The currency in which the deposits of assets and their metadata are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The amount reserved from an account for each asset it creates.
+	type AssetDeposit: Get<DepositBalanceOf<Self>>;
+
+	/// The amount reserved from an asset's owner when it sets the asset's metadata.
+	type MetadataDepositBase: Get<DepositBalanceOf<Self>>;
+
+	/// The amount reserved for each byte of an asset's name and symbol.
+	type MetadataDepositPerByte: Get<DepositBalanceOf<Self>>;
+
+	/// The longest name or symbol an asset may have, in bytes.
+	type StringLimit: Get<u32>;
+
+	/// The most accounts that may hold an asset at once.
+	type MaxHolders: Get<u32>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+/// An asset and the account that owns it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct AssetDetails<Balance, AccountId, DepositBalance> {
+	/// The account that created the asset and may mint, burn, freeze and describe it.
+	pub owner: AccountId,
+	/// The amount reserved from the owner for the asset.
+	pub deposit: DepositBalance,
+	/// The total amount of the asset held by all accounts.
+	pub supply: Balance,
+	/// The smallest balance of the asset an account may hold.
+	pub min_balance: Balance,
+	/// The number of accounts holding the asset.
+	pub accounts: u32,
+	/// Whether transfers of the asset are suspended.
+	pub is_frozen: bool,
+}
+
+/// An account's holding of an asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct AssetBalance<Balance> {
+	/// The amount held.
+	pub balance: Balance,
+	/// Whether the account may not transfer the asset.
+	pub is_frozen: bool,
+}
+
+/// The name, symbol and decimals by which wallets show an asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct AssetMetadata<DepositBalance> {
+	/// The amount reserved from the asset's owner for the metadata.
+	pub deposit: DepositBalance,
+	/// The name of the asset.
+	pub name: Vec<u8>,
+	/// The ticker symbol of the asset.
+	pub symbol: Vec<u8>,
+	/// The number of decimals wallets show balances of the asset with.
+	pub decimals: u8,
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Assets {
+		/// The assets that have been created.
+		Asset get(fn asset): map hasher(blake2_128_concat) T::AssetId => Option<AssetDetailsOf<T>>;
+
+		/// The holdings of each account, keyed by account first so that all of an account's
+		/// assets can be listed. Only non-zero balances are kept.
+		Account get(fn account): double_map
+			hasher(blake2_128_concat) T::AccountId,
+			hasher(blake2_128_concat) T::AssetId
+			=> Option<AssetBalance<T::Balance>>;
+
+		/// The metadata of assets whose owner has set it.
+		Metadata get(fn metadata): map hasher(blake2_128_concat) T::AssetId
+			=> Option<AssetMetadata<DepositBalanceOf<T>>>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		AssetId = <T as Trait>::AssetId,
+		Balance = <T as Trait>::Balance,
+	{
+		/// An asset was created. [asset_id, owner]
+		Created(AssetId, AccountId),
+		/// An amount of an asset was minted to an account. [asset_id, beneficiary, amount]
+		Issued(AssetId, AccountId, Balance),
+		/// An amount of an asset was transferred. [asset_id, from, to, amount]
+		Transferred(AssetId, AccountId, AccountId, Balance),
+		/// An amount of an asset was burned from an account. [asset_id, who, amount]
+		Burned(AssetId, AccountId, Balance),
+		/// An account may no longer transfer an asset. [asset_id, who]
+		Frozen(AssetId, AccountId),
+		/// An account may transfer an asset again. [asset_id, who]
+		Thawed(AssetId, AccountId),
+		/// Transfers of an asset were suspended. [asset_id]
+		AssetFrozen(AssetId),
+		/// Transfers of an asset were resumed. [asset_id]
+		AssetThawed(AssetId),
+		/// The metadata of an asset was set. [asset_id, name, symbol, decimals]
+		MetadataSet(AssetId, Vec<u8>, Vec<u8>, u8),
+		/// The metadata of an asset was removed. [asset_id]
+		MetadataCleared(AssetId),
+		/// An asset was destroyed. [asset_id]
+		Destroyed(AssetId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// No asset has the given id.
+		Unknown,
+		/// An asset with the given id already exists.
+		InUse,
+		/// Only the owner of the asset may make this call.
+		NoPermission,
+		/// The minimum balance of an asset must not be zero.
+		MinBalanceZero,
+		/// The amount to transfer must not be zero.
+		AmountZero,
+		/// The balance is too low to send, or would be too low to hold, the amount.
+		BalanceLow,
+		/// The account holds none of the asset.
+		BalanceZero,
+		/// The asset, or the account's holding of it, is frozen.
+		Frozen,
+		/// Minting the amount would overflow the asset's supply.
+		Overflow,
+		/// The asset cannot be destroyed while accounts still hold it.
+		InCirculation,
+		/// The name or symbol is longer than `StringLimit`.
+		BadMetadata,
+		/// The asset has no metadata to clear.
+		NoMetadata,
+		/// The asset already has `MaxHolders` holders, so no other account may receive it.
+		TooManyHolders,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The amount reserved from an account for each asset it creates.
+		const AssetDeposit: DepositBalanceOf<T> = T::AssetDeposit::get();
+
+		/// The amount reserved from an asset's owner when it sets the asset's metadata.
+		const MetadataDepositBase: DepositBalanceOf<T> = T::MetadataDepositBase::get();
+
+		/// The amount reserved for each byte of an asset's name and symbol.
+		const MetadataDepositPerByte: DepositBalanceOf<T> = T::MetadataDepositPerByte::get();
+
+		/// The longest name or symbol an asset may have, in bytes.
+		const StringLimit: u32 = T::StringLimit::get();
+
+		/// The most accounts that may hold an asset at once.
+		const MaxHolders: u32 = T::MaxHolders::get();
+
+		fn deposit_event() = default;
+
+		/// Create a new asset with no supply, owned by the caller, under the unused `id`.
+		///
+		/// `AssetDeposit` is reserved from the caller until the asset is destroyed. No account may
+		/// hold less than `min_balance` of the asset unless it holds none.
+		#[weight = T::WeightInfo::create()]
+		pub fn create(origin, id: T::AssetId, min_balance: T::Balance) -> dispatch::DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(!<Asset<T>>::contains_key(&id), Error::<T>::InUse);
+			ensure!(!min_balance.is_zero(), Error::<T>::MinBalanceZero);
+
+			let deposit = T::AssetDeposit::get();
+			T::Currency::reserve(&owner, deposit)?;
+
+			<Asset<T>>::insert(&id, AssetDetails {
+				owner: owner.clone(),
+				deposit,
+				supply: Zero::zero(),
+				min_balance,
+				accounts: 0,
+				is_frozen: false,
+			});
+
+			Self::deposit_event(RawEvent::Created(id, owner));
+			Ok(())
+		}
+
+		/// Destroy an asset whose whole supply has been burned, and return the deposits of the
+		/// asset and its metadata to its owner.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::destroy()]
+		pub fn destroy(origin, id: T::AssetId) -> dispatch::DispatchResult {
+			let (owner, details) = Self::owned(origin, &id)?;
+
+			ensure!(details.supply.is_zero(), Error::<T>::InCirculation);
+
+			<Asset<T>>::remove(&id);
+			let metadata_deposit = <Metadata<T>>::take(&id).map_or_else(Zero::zero, |m| m.deposit);
+			T::Currency::unreserve(&owner, details.deposit.saturating_add(metadata_deposit));
+
+			Self::deposit_event(RawEvent::Destroyed(id));
+			Ok(())
+		}
+
+		/// Mint `amount` of an asset to `beneficiary`, which must then hold at least the asset's
+		/// minimum balance. A new holder must not take the asset over `MaxHolders` holders.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::mint()]
+		pub fn mint(
+			origin,
+			id: T::AssetId,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			amount: T::Balance,
+		) -> dispatch::DispatchResult {
+			let (_, mut details) = Self::owned(origin, &id)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			details.supply = details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+			let mut account = <Account<T>>::get(&beneficiary, &id).unwrap_or_default();
+			if account.balance.is_zero() {
+				details.accounts = Self::add_holder(details.accounts)?;
+			}
+			// Cannot overflow, as no balance exceeds the supply.
+			account.balance = account.balance.saturating_add(amount);
+			ensure!(account.balance >= details.min_balance, Error::<T>::BalanceLow);
+
+			Self::set_account(&beneficiary, &id, account);
+			<Asset<T>>::insert(&id, details);
+
+			Self::deposit_event(RawEvent::Issued(id, beneficiary, amount));
+			Ok(())
+		}
+
+		/// Burn up to `amount` of an asset from `who`. If less than the asset's minimum balance
+		/// would be left, the account's whole balance is burned instead.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::burn()]
+		pub fn burn(
+			origin,
+			id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+			amount: T::Balance,
+		) -> dispatch::DispatchResult {
+			let (_, mut details) = Self::owned(origin, &id)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let mut account = <Account<T>>::get(&who, &id).ok_or(Error::<T>::BalanceZero)?;
+			let mut burned = amount.min(account.balance);
+			if account.balance - burned < details.min_balance {
+				burned = account.balance;
+			}
+			account.balance -= burned;
+			details.supply = details.supply.saturating_sub(burned);
+			if account.balance.is_zero() {
+				details.accounts = details.accounts.saturating_sub(1);
+			}
+
+			Self::set_account(&who, &id, account);
+			<Asset<T>>::insert(&id, details);
+
+			Self::deposit_event(RawEvent::Burned(id, who, burned));
+			Ok(())
+		}
+
+		/// Transfer `amount` of an asset from the caller to `target`.
+		///
+		/// `target` must then hold at least the asset's minimum balance. If the caller would be
+		/// left with less than that, its whole balance is transferred instead. A new holder must
+		/// not take the asset over `MaxHolders` holders, unless the caller stops holding it.
+		#[weight = T::WeightInfo::transfer()]
+		pub fn transfer(
+			origin,
+			id: T::AssetId,
+			target: <T::Lookup as StaticLookup>::Source,
+			amount: T::Balance,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+
+			let mut details = Self::asset(&id).ok_or(Error::<T>::Unknown)?;
+			ensure!(!details.is_frozen, Error::<T>::Frozen);
+			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+
+			let mut source = <Account<T>>::get(&who, &id).ok_or(Error::<T>::BalanceLow)?;
+			ensure!(!source.is_frozen, Error::<T>::Frozen);
+			ensure!(source.balance >= amount, Error::<T>::BalanceLow);
+			if who == target {
+				return Ok(());
+			}
+
+			let mut amount = amount;
+			if source.balance - amount < details.min_balance {
+				amount = source.balance;
+			}
+			let mut dest = <Account<T>>::get(&target, &id).unwrap_or_default();
+			let new_holder = dest.balance.is_zero();
+			// Cannot overflow, as no balance exceeds the supply.
+			dest.balance = dest.balance.saturating_add(amount);
+			ensure!(dest.balance >= details.min_balance, Error::<T>::BalanceLow);
+			source.balance -= amount;
+
+			let source_left = source.balance.is_zero();
+			match (new_holder, source_left) {
+				(true, false) => details.accounts = Self::add_holder(details.accounts)?,
+				(false, true) => details.accounts = details.accounts.saturating_sub(1),
+				// The target takes the caller's place, or the holders stay the same.
+				_ => {},
+			}
+
+			Self::set_account(&who, &id, source);
+			Self::set_account(&target, &id, dest);
+			if new_holder != source_left {
+				<Asset<T>>::insert(&id, details);
+			}
+
+			Self::deposit_event(RawEvent::Transferred(id, who, target, amount));
+			Ok(())
+		}
+
+		/// Stop `who` from transferring an asset it holds. The owner may still burn from it, and
+		/// burning its whole balance also thaws it.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::freeze()]
+		pub fn freeze(
+			origin,
+			id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			let who = Self::set_account_frozen(origin, &id, who, true)?;
+			Self::deposit_event(RawEvent::Frozen(id, who));
+			Ok(())
+		}
+
+		/// Allow a frozen account to transfer an asset again.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::thaw()]
+		pub fn thaw(
+			origin,
+			id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			let who = Self::set_account_frozen(origin, &id, who, false)?;
+			Self::deposit_event(RawEvent::Thawed(id, who));
+			Ok(())
+		}
+
+		/// Suspend all transfers of an asset. The owner may still mint and burn it.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::freeze_asset()]
+		pub fn freeze_asset(origin, id: T::AssetId) -> dispatch::DispatchResult {
+			let (_, mut details) = Self::owned(origin, &id)?;
+			details.is_frozen = true;
+			<Asset<T>>::insert(&id, details);
+
+			Self::deposit_event(RawEvent::AssetFrozen(id));
+			Ok(())
+		}
+
+		/// Resume transfers of a frozen asset.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::thaw_asset()]
+		pub fn thaw_asset(origin, id: T::AssetId) -> dispatch::DispatchResult {
+			let (_, mut details) = Self::owned(origin, &id)?;
+			details.is_frozen = false;
+			<Asset<T>>::insert(&id, details);
+
+			Self::deposit_event(RawEvent::AssetThawed(id));
+			Ok(())
+		}
+
+		/// Set or replace the name, symbol and decimals of an asset.
+		///
+		/// The metadata deposit is reserved from the owner, or adjusted by the difference if the
+		/// asset already had metadata.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::set_metadata(name.len() as u32, symbol.len() as u32)]
+		pub fn set_metadata(
+			origin,
+			id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+		) -> dispatch::DispatchResult {
+			let (owner, _) = Self::owned(origin, &id)?;
+
+			let limit = T::StringLimit::get() as usize;
+			ensure!(name.len() <= limit && symbol.len() <= limit, Error::<T>::BadMetadata);
+
+			let old_deposit = <Metadata<T>>::get(&id).map_or_else(Zero::zero, |m| m.deposit);
+			let deposit = T::MetadataDepositPerByte::get()
+				.saturating_mul(((name.len() + symbol.len()) as u32).into())
+				.saturating_add(T::MetadataDepositBase::get());
+			if deposit > old_deposit {
+				T::Currency::reserve(&owner, deposit - old_deposit)?;
+			} else {
+				T::Currency::unreserve(&owner, old_deposit - deposit);
+			}
+
+			<Metadata<T>>::insert(&id, AssetMetadata {
+				deposit,
+				name: name.clone(),
+				symbol: symbol.clone(),
+				decimals,
+			});
+
+			Self::deposit_event(RawEvent::MetadataSet(id, name, symbol, decimals));
+			Ok(())
+		}
+
+		/// Remove the metadata of an asset and return its deposit.
+		///
+		/// The dispatch origin for this call must be the asset's owner.
+		#[weight = T::WeightInfo::clear_metadata()]
+		pub fn clear_metadata(origin, id: T::AssetId) -> dispatch::DispatchResult {
+			let (owner, _) = Self::owned(origin, &id)?;
+
+			let metadata = <Metadata<T>>::take(&id).ok_or(Error::<T>::NoMetadata)?;
+			T::Currency::unreserve(&owner, metadata.deposit);
+
+			Self::deposit_event(RawEvent::MetadataCleared(id));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The amount of asset `id` held by `who`.
+	pub fn balance(id: T::AssetId, who: T::AccountId) -> T::Balance {
+		<Account<T>>::get(&who, &id).map_or_else(Zero::zero, |account| account.balance)
+	}
+
+	/// Every asset `who` holds, with the amount it holds, in no particular order.
+	pub fn account_balances(who: T::AccountId) -> Vec<(T::AssetId, T::Balance)> {
+		<Account<T>>::iter_prefix(&who).map(|(id, account)| (id, account.balance)).collect()
+	}
+
+	/// The owner and details of asset `id`, if `origin` is signed by its owner.
+	fn owned(
+		origin: T::Origin,
+		id: &T::AssetId,
+	) -> Result<(T::AccountId, AssetDetailsOf<T>), DispatchError> {
+		let who = ensure_signed(origin)?;
+		let details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+		ensure!(details.owner == who, Error::<T>::NoPermission);
+		Ok((who, details))
+	}
+
+	/// Freeze or thaw the holding of asset `id` by `who`, if `origin` is signed by its owner.
+	fn set_account_frozen(
+		origin: T::Origin,
+		id: &T::AssetId,
+		who: <T::Lookup as StaticLookup>::Source,
+		is_frozen: bool,
+	) -> Result<T::AccountId, DispatchError> {
+		Self::owned(origin, id)?;
+		let who = T::Lookup::lookup(who)?;
+
+		<Account<T>>::try_mutate(&who, id, |account| {
+			let account = account.as_mut().ok_or(Error::<T>::BalanceZero)?;
+			account.is_frozen = is_frozen;
+			Ok::<_, DispatchError>(())
+		})?;
+		Ok(who)
+	}
+
+	/// `accounts` with one more holder, if that does not exceed `MaxHolders`.
+	fn add_holder(accounts: u32) -> Result<u32, DispatchError> {
+		ensure!(accounts < T::MaxHolders::get(), Error::<T>::TooManyHolders);
+		Ok(accounts + 1)
+	}
+
+	/// Store the holding of asset `id` by `who`, removing it once nothing is left.
+	fn set_account(who: &T::AccountId, id: &T::AssetId, account: AssetBalance<T::Balance>) {
+		if account.balance.is_zero() {
+			<Account<T>>::remove(who, id);
+		} else {
+			<Account<T>>::insert(who, id, account);
+		}
+	}
+}
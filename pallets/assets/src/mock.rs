@@ -0,0 +1,118 @@
+use crate::{Module, Trait};
+use sp_core::H256;
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+};
+use frame_system as system;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod assets {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_balances<T>,
+		assets<T>,
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const ExistentialDeposit: u64 = 1;
+	pub const AssetDeposit: u64 = 10;
+	pub const MetadataDepositBase: u64 = 5;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub const StringLimit: u32 = 8;
+	pub const MaxHolders: u32 = 3;
+}
+
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type Balance = u64;
+	type AssetId = u32;
+	type Currency = Balances;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type StringLimit = StringLimit;
+	type MaxHolders = MaxHolders;
+	type WeightInfo = ();
+}
+
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Assets = Module<Test>;
+
+// Build genesis storage with accounts 1 to 3 endowed with 100 units each of the native currency.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	// Events are not deposited in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+// The last event deposited by the assets pallet.
+pub fn last_event() -> crate::RawEvent<u64, u32, u64> {
+	System::events().into_iter()
+		.filter_map(|record| match record.event {
+			TestEvent::assets(event) => Some(event),
+			_ => None,
+		})
+		.last()
+		.expect("the assets pallet deposited an event")
+}
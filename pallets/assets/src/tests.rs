@@ -0,0 +1,299 @@
+use crate::{AssetBalance, AssetMetadata, Error, RawEvent, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::ReservableCurrency};
+use sp_runtime::{DispatchResult, traits::BadOrigin};
+
+// Have account 1 create asset 0 with a minimum balance of 10 and mint 100 of it to itself.
+fn create_and_mint() {
+	assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+	assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+}
+
+// Have `who` name asset 0 "Token", with symbol "TKN" and 12 decimals.
+fn set_token_metadata(who: u64) -> DispatchResult {
+	Assets::set_metadata(Origin::signed(who), 0, b"Token".to_vec(), b"TKN".to_vec(), 12)
+}
+
+#[test]
+fn create_reserves_the_asset_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+		assert_eq!(last_event(), RawEvent::Created(0, 1));
+
+		let details = Assets::asset(0).unwrap();
+		assert_eq!(details.owner, 1);
+		assert_eq!(details.supply, 0);
+		assert_eq!(details.min_balance, 10);
+		assert_eq!(details.accounts, 0);
+		assert_eq!(Balances::reserved_balance(1), 10);
+	});
+}
+
+#[test]
+fn create_rejects_used_ids_and_a_zero_minimum() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+		assert_noop!(Assets::create(Origin::signed(2), 0, 10), Error::<Test>::InUse);
+		assert_noop!(Assets::create(Origin::signed(2), 1, 0), Error::<Test>::MinBalanceZero);
+		assert_noop!(Assets::create(Origin::none(), 1, 10), BadOrigin);
+	});
+}
+
+#[test]
+fn create_fails_without_the_deposit() {
+	new_test_ext().execute_with(|| {
+		// Account 4 has no native balance.
+		assert!(Assets::create(Origin::signed(4), 0, 10).is_err());
+		assert_eq!(Assets::asset(0), None);
+	});
+}
+
+#[test]
+fn only_the_owner_mints() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+		assert_noop!(Assets::mint(Origin::signed(2), 0, 2, 100), Error::<Test>::NoPermission);
+		assert_noop!(Assets::mint(Origin::signed(1), 1, 2, 100), Error::<Test>::Unknown);
+
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+		assert_eq!(last_event(), RawEvent::Issued(0, 2, 100));
+		assert_eq!(Assets::balance(0, 2), 100);
+		assert_eq!(Assets::asset(0).unwrap().supply, 100);
+	});
+}
+
+#[test]
+fn mint_respects_the_minimum_balance_and_the_supply() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+		assert_noop!(Assets::mint(Origin::signed(1), 0, 2, 9), Error::<Test>::BalanceLow);
+
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, u64::max_value()));
+		assert_noop!(Assets::mint(Origin::signed(1), 0, 3, 10), Error::<Test>::Overflow);
+	});
+}
+
+#[test]
+fn transfer_moves_balances() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 40));
+		assert_eq!(last_event(), RawEvent::Transferred(0, 1, 2, 40));
+		assert_eq!(Assets::balance(0, 1), 60);
+		assert_eq!(Assets::balance(0, 2), 40);
+		assert_eq!(Assets::asset(0).unwrap().supply, 100);
+	});
+}
+
+#[test]
+fn transfer_fails_for_too_low_balances() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 101), Error::<Test>::BalanceLow);
+		assert_noop!(Assets::transfer(Origin::signed(2), 0, 1, 10), Error::<Test>::BalanceLow);
+		// Account 2 would hold less than the minimum balance.
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 9), Error::<Test>::BalanceLow);
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 0), Error::<Test>::AmountZero);
+		assert_noop!(Assets::transfer(Origin::signed(1), 1, 2, 10), Error::<Test>::Unknown);
+	});
+}
+
+#[test]
+fn transfer_takes_a_remainder_below_the_minimum_along() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 95));
+		assert_eq!(last_event(), RawEvent::Transferred(0, 1, 2, 100));
+		assert_eq!(Assets::balance(0, 1), 0);
+		assert_eq!(Assets::account(1, 0), None);
+		assert_eq!(Assets::balance(0, 2), 100);
+	});
+}
+
+#[test]
+fn holders_are_limited_to_max_holders() {
+	new_test_ext().execute_with(|| {
+		let holders = || Assets::asset(0).unwrap().accounts;
+		create_and_mint();
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 3, 100));
+		assert_eq!(holders(), 3);
+
+		// Existing holders may receive more, but no fourth account.
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 10));
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+		assert_noop!(Assets::mint(Origin::signed(1), 0, 4, 100), Error::<Test>::TooManyHolders);
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 4, 40), Error::<Test>::TooManyHolders);
+		assert_eq!(holders(), 3);
+
+		// A holder passing on its whole balance makes way for the new one.
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 4, 90));
+		assert_eq!(holders(), 3);
+		// Emptying a holding frees a place.
+		assert_ok!(Assets::transfer(Origin::signed(4), 0, 2, 90));
+		assert_eq!(holders(), 2);
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 3, 100));
+		assert_eq!(holders(), 1);
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 5, 100));
+		assert_eq!(holders(), 2);
+	});
+}
+
+#[test]
+fn burn_reduces_the_supply() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+		assert_noop!(Assets::burn(Origin::signed(2), 0, 2, 10), Error::<Test>::NoPermission);
+
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 2, 20));
+		assert_eq!(last_event(), RawEvent::Burned(0, 2, 20));
+		assert_eq!(Assets::balance(0, 2), 30);
+		assert_eq!(Assets::asset(0).unwrap().supply, 80);
+
+		// Burning more than is held, or leaving less than the minimum, burns everything.
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 2, 25));
+		assert_eq!(last_event(), RawEvent::Burned(0, 2, 30));
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 1, 1_000));
+		assert_eq!(last_event(), RawEvent::Burned(0, 1, 50));
+		assert_eq!(Assets::asset(0).unwrap().supply, 0);
+		assert_noop!(Assets::burn(Origin::signed(1), 0, 1, 10), Error::<Test>::BalanceZero);
+	});
+}
+
+#[test]
+fn frozen_accounts_cannot_transfer() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_noop!(Assets::freeze(Origin::signed(2), 0, 1), Error::<Test>::NoPermission);
+		assert_noop!(Assets::freeze(Origin::signed(1), 0, 2), Error::<Test>::BalanceZero);
+
+		assert_ok!(Assets::freeze(Origin::signed(1), 0, 1));
+		assert_eq!(last_event(), RawEvent::Frozen(0, 1));
+		assert_eq!(Assets::account(1, 0), Some(AssetBalance { balance: 100, is_frozen: true }));
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::Frozen);
+
+		// Frozen accounts can still receive.
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 20));
+		assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 10));
+
+		assert_ok!(Assets::thaw(Origin::signed(1), 0, 1));
+		assert_eq!(last_event(), RawEvent::Thawed(0, 1));
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+	});
+}
+
+#[test]
+fn frozen_assets_cannot_be_transferred() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_noop!(Assets::freeze_asset(Origin::signed(2), 0), Error::<Test>::NoPermission);
+
+		assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::AssetFrozen(0));
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::Frozen);
+		// The owner still mints and burns.
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 10));
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 2, 10));
+
+		assert_ok!(Assets::thaw_asset(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::AssetThawed(0));
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+	});
+}
+
+#[test]
+fn set_metadata_reserves_a_deposit_by_length() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+		assert_noop!(set_token_metadata(2), Error::<Test>::NoPermission);
+
+		assert_ok!(set_token_metadata(1));
+		assert_eq!(last_event(), RawEvent::MetadataSet(0, b"Token".to_vec(), b"TKN".to_vec(), 12));
+		assert_eq!(Assets::metadata(0), Some(AssetMetadata {
+			deposit: 5 + 8,
+			name: b"Token".to_vec(),
+			symbol: b"TKN".to_vec(),
+			decimals: 12,
+		}));
+		assert_eq!(Balances::reserved_balance(1), 10 + 5 + 8);
+
+		// Shorter metadata returns the difference.
+		assert_ok!(Assets::set_metadata(Origin::signed(1), 0, b"T".to_vec(), b"T".to_vec(), 0));
+		assert_eq!(Balances::reserved_balance(1), 10 + 5 + 2);
+	});
+}
+
+#[test]
+fn set_metadata_rejects_long_strings() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(1), 0, b"Long token".to_vec(), b"LT".to_vec(), 12),
+			Error::<Test>::BadMetadata,
+		);
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(1), 0, b"Token".to_vec(), vec![b'T'; 9], 12),
+			Error::<Test>::BadMetadata,
+		);
+	});
+}
+
+#[test]
+fn set_metadata_fails_without_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+		assert_ok!(Balances::reserve(&1, 85));
+		assert!(set_token_metadata(1).is_err());
+		assert_eq!(Assets::metadata(0), None);
+	});
+}
+
+#[test]
+fn clear_metadata_returns_its_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::create(Origin::signed(1), 0, 10));
+		assert_noop!(Assets::clear_metadata(Origin::signed(1), 0), Error::<Test>::NoMetadata);
+		assert_ok!(set_token_metadata(1));
+		assert_noop!(Assets::clear_metadata(Origin::signed(2), 0), Error::<Test>::NoPermission);
+
+		assert_ok!(Assets::clear_metadata(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::MetadataCleared(0));
+		assert_eq!(Assets::metadata(0), None);
+		assert_eq!(Balances::reserved_balance(1), 10);
+	});
+}
+
+#[test]
+fn destroy_waits_for_the_supply_to_be_burned() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(set_token_metadata(1));
+		assert_noop!(Assets::destroy(Origin::signed(2), 0), Error::<Test>::NoPermission);
+		assert_noop!(Assets::destroy(Origin::signed(1), 0), Error::<Test>::InCirculation);
+
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::destroy(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::Destroyed(0));
+		assert_eq!(Assets::asset(0), None);
+		assert_eq!(Assets::metadata(0), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// The id can be used again.
+		assert_ok!(Assets::create(Origin::signed(2), 0, 1));
+	});
+}
+
+#[test]
+fn account_balances_lists_every_asset_held() {
+	new_test_ext().execute_with(|| {
+		create_and_mint();
+		assert_ok!(Assets::create(Origin::signed(2), 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(2), 1, 1, 7));
+		assert_ok!(Assets::create(Origin::signed(3), 2, 1));
+
+		let mut balances = Assets::account_balances(1);
+		balances.sort();
+		assert_eq!(balances, vec![(0, 100), (1, 7)]);
+		assert_eq!(Assets::account_balances(3), vec![]);
+	});
+}
@@ -0,0 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Chain Info Pallet
+//!
+//! Exposes facts about the chain that wallets need to talk to it as constants in the runtime
+//! metadata, where they can read them without a chain spec. The pallet has no storage, calls or
+//! events.
+
+use frame_support::{decl_module, traits::Get};
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	/// The SS58 prefix of the chain's addresses.
+	type SS58Prefix: Get<u8>;
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		/// The SS58 prefix of the chain's addresses.
+		const SS58Prefix: u8 = T::SS58Prefix::get();
+	}
+}
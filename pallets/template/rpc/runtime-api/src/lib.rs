@@ -0,0 +1,19 @@
+//! Runtime API definition for the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// Read the template pallet's state without computing storage keys by hand.
+	pub trait TemplateApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The value most recently stored by any account.
+		fn get_something() -> Option<u64>;
+		/// The value stored by `who`.
+		fn get_something_of(who: AccountId) -> Option<u64>;
+		/// The value `cause_error` would store for `who`, or `None` if it would fail.
+		fn next_something_of(who: AccountId) -> Option<u64>;
+	}
+}
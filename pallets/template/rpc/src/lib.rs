@@ -0,0 +1,109 @@
+//! RPC interface for the template pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_template_rpc_runtime_api::TemplateApi as TemplateRuntimeApi;
+
+/// Template pallet RPC methods.
+#[rpc]
+pub trait TemplateApi<BlockHash, AccountId> {
+	/// The value most recently stored by any account, at the given block or the best block.
+	#[rpc(name = "template_getSomething")]
+	fn get_something(&self, at: Option<BlockHash>) -> Result<Option<u64>>;
+
+	/// The value stored by `who`, at the given block or the best block.
+	#[rpc(name = "template_getSomethingOf")]
+	fn get_something_of(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<u64>>;
+
+	/// The value `cause_error` would store for `who`, or `null` if the call would fail.
+	#[rpc(name = "template_nextSomethingOf")]
+	fn next_something_of(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<u64>>;
+}
+
+/// Implements the [`TemplateApi`] RPC trait for interacting with the template pallet.
+pub struct Template<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Template<C, B> {
+	/// Create new `Template` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query the template pallet.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId> TemplateApi<<Block as BlockT>::Hash, AccountId> for Template<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TemplateRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	fn get_something(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Option<u64>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.get_something(&at).map_err(runtime_error)
+	}
+
+	fn get_something_of(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<u64>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.get_something_of(&at, who).map_err(runtime_error)
+	}
+
+	fn next_something_of(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<u64>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.next_something_of(&at, who).map_err(runtime_error)
+	}
+}
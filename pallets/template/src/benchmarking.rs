@@ -0,0 +1,104 @@
+//! Benchmarking setup for pallet-template
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{StorageMap, StorageValue};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup, Zero};
+
+const SEED: u32 = 0;
+
+// Fill `History` with one entry more than `MaxHistory`, as if it had been lowered, so that
+// recording the benchmarked change evicts as many entries as any change does.
+fn fill_history<T: Trait>() {
+	let who: T::AccountId = account("history", 0, SEED);
+	let entries = u64::from(T::MaxHistory::get()) + 1;
+	for index in 0..entries {
+		<History<T>>::insert(index, HistoryEntry {
+			value: Some(index),
+			who: who.clone(),
+			block_number: Zero::zero(),
+		});
+	}
+	HistoryEnd::put(entries);
+}
+
+// Give `who` enough free balance to pay the storage deposit.
+fn fund<T: Trait>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+benchmarks! {
+	_ { }
+
+	do_something {
+		fill_history::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), 42)
+	verify {
+		assert_eq!(Module::<T>::something_of(&caller), Some(42));
+		assert_eq!(T::Currency::reserved_balance(&caller), T::StorageDeposit::get());
+	}
+
+	cause_error {
+		fill_history::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		<SomethingOf<T>>::insert(&caller, 42);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Module::<T>::something_of(&caller), Some(43));
+	}
+
+	clear_something {
+		fill_history::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		Module::<T>::do_something(RawOrigin::Signed(caller.clone()).into(), 42)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Module::<T>::something_of(&caller), None);
+		assert_eq!(T::Currency::reserved_balance(&caller), 0u32.into());
+	}
+
+	force_set_something {
+		fill_history::<T>();
+		let origin = T::ForceOrigin::successful_origin();
+		let who: T::AccountId = account("who", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+	}: _<T::Origin>(origin, who_lookup, 42)
+	verify {
+		assert_eq!(Module::<T>::something_of(&who), Some(42));
+	}
+
+	kill_something {
+		fill_history::<T>();
+		let origin = T::ForceOrigin::successful_origin();
+		let who: T::AccountId = account("who", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		fund::<T>(&who);
+		Module::<T>::do_something(RawOrigin::Signed(who.clone()).into(), 42)?;
+	}: _<T::Origin>(origin, who_lookup)
+	verify {
+		assert_eq!(Module::<T>::something_of(&who), None);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_do_something::<Test>());
+			assert_ok!(test_benchmark_cause_error::<Test>());
+			assert_ok!(test_benchmark_clear_something::<Test>());
+			assert_ok!(test_benchmark_force_set_something::<Test>());
+			assert_ok!(test_benchmark_kill_something::<Test>());
+		});
+	}
+}
@@ -4,8 +4,17 @@
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// https://substrate.dev/docs/en/knowledgebase/runtime/frame
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, traits::Get};
+use codec::{Encode, Decode};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure,
+	traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+};
 use frame_system::ensure_signed;
+use sp_runtime::traits::StaticLookup;
+use sp_std::prelude::*;
+
+pub mod migrations;
+pub mod weights;
 
 #[cfg(test)]
 mod mock;
@@ -13,10 +22,114 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub use weights::WeightInfo;
+
+/// The most `History` entries a single change evicts. One more than it records, so that `History`
+/// shrinks back to `MaxHistory` after it was lowered.
+pub const MAX_EVICTIONS_PER_CHANGE: u64 = 2;
+
+type BalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The smallest value an account may store.
+	type MinValue: Get<u64>;
+
+	/// The largest value an account may store.
+	type MaxValue: Get<u64>;
+
+	/// What `cause_error` does when incrementing would go past `MaxValue`.
+	type OverflowPolicy: OverflowPolicy;
+
+	/// The origin allowed to set or remove any account's value.
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The number of most recent changes kept in `History`.
+	type MaxHistory: Get<u32>;
+
+	/// The currency in which storage deposits are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The amount reserved from an account when it first stores a value.
+	type StorageDeposit: Get<BalanceOf<Self>>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+/// Decides the value that follows a stored value when it is incremented.
+pub trait OverflowPolicy {
+	/// The value following `value` in the range `min..=max`, or `None` if incrementing should
+	/// fail.
+	fn next(value: u64, min: u64, max: u64) -> Option<u64>;
+}
+
+/// Fail with `StorageOverflow` once `MaxValue` is reached.
+pub struct FailOnOverflow;
+
+impl OverflowPolicy for FailOnOverflow {
+	fn next(value: u64, _min: u64, max: u64) -> Option<u64> {
+		value.checked_add(1).filter(|next| *next <= max)
+	}
+}
+
+/// Stay at `MaxValue` once it is reached.
+pub struct SaturateOnOverflow;
+
+impl OverflowPolicy for SaturateOnOverflow {
+	fn next(value: u64, _min: u64, max: u64) -> Option<u64> {
+		Some(value.saturating_add(1).min(max))
+	}
+}
+
+/// Start again from `MinValue` once `MaxValue` is reached.
+pub struct WrapOnOverflow;
+
+impl OverflowPolicy for WrapOnOverflow {
+	fn next(value: u64, min: u64, max: u64) -> Option<u64> {
+		// A value below `MinValue`, stored before it was raised, restarts from it too.
+		if value < min || value >= max {
+			Some(min)
+		} else {
+			Some(value + 1)
+		}
+	}
+}
+
+/// Storage layout versions of this pallet, used to decide which migrations still need to run.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Releases {
+	/// `Something` and `SomethingOf` hold `u32` values. Chains from before `SomethingOf` was
+	/// added may have a global value only.
+	V1_0_0,
+	/// `Something` and `SomethingOf` hold `u64` values.
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// A recorded change to an account's value.
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct HistoryEntry<AccountId, BlockNumber> {
+	/// The new value, or `None` if the value was removed.
+	pub value: Option<u64>,
+	/// The account whose value changed.
+	pub who: AccountId,
+	/// The block in which the change happened.
+	pub block_number: BlockNumber,
 }
 
 // The pallet's runtime storage items.
@@ -28,7 +141,48 @@ decl_storage! {
 	trait Store for Module<T: Trait> as TemplateModule {
 		// Learn more about declaring storage items:
 		// https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
-		Something get(fn something): Option<u32>;
+		Something get(fn something): Option<u64>;
+		/// The value most recently stored by each account.
+		SomethingOf get(fn something_of): map hasher(blake2_128_concat) T::AccountId => Option<u64>;
+
+		/// The most recent changes to stored values, keyed by sequence number. Entries older than
+		/// the last `MaxHistory` are evicted as new ones are recorded, at most
+		/// `MAX_EVICTIONS_PER_CHANGE` per change.
+		History get(fn history_entry):
+			map hasher(twox_64_concat) u64 => Option<HistoryEntry<T::AccountId, T::BlockNumber>>;
+		/// Sequence number of the oldest entry kept in `History`.
+		HistoryStart get(fn history_start): u64;
+		/// Sequence number the next entry recorded in `History` will get.
+		HistoryEnd get(fn history_end): u64;
+
+		/// The deposit reserved from each account that stored a value. Values set by genesis, by
+		/// the force origin or by a migration have no deposit.
+		Deposits get(fn deposit_of): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
+		/// Storage version of the pallet.
+		///
+		/// This is set to the latest version at genesis, and chains that predate it read it as
+		/// `V1_0_0`.
+		StorageVersion build(|_: &GenesisConfig<T>| Releases::V2_0_0): Releases;
+	}
+	add_extra_genesis {
+		/// The initial global value, if any.
+		config(something): Option<u64>;
+		/// Initial values stored by individual accounts.
+		config(something_of): Vec<(T::AccountId, u64)>;
+		build(|config: &GenesisConfig<T>| {
+			assert!(
+				config.something.iter().chain(config.something_of.iter().map(|(_, v)| v))
+					.all(|value| Module::<T>::in_range(*value)),
+				"Genesis values must lie between `MinValue` and `MaxValue`",
+			);
+			if let Some(something) = config.something {
+				Something::put(something);
+			}
+			for (who, something) in &config.something_of {
+				<SomethingOf<T>>::insert(who, something);
+			}
+		});
 	}
 }
 
@@ -38,7 +192,13 @@ decl_event!(
 	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
-		SomethingStored(u32, AccountId),
+		SomethingStored(u64, AccountId),
+		/// An account removed its stored value. [who]
+		SomethingCleared(AccountId),
+		/// The force origin set an account's value. [something, who]
+		SomethingForceSet(u64, AccountId),
+		/// The force origin removed an account's value. [who]
+		SomethingKilled(AccountId),
 	}
 );
 
@@ -49,6 +209,8 @@ decl_error! {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// The value lies outside `MinValue..=MaxValue`.
+		ValueOutOfRange,
 	}
 }
 
@@ -60,20 +222,57 @@ decl_module! {
 		// Errors must be initialized if they are used by the pallet.
 		type Error = Error<T>;
 
+		/// The smallest value an account may store.
+		const MinValue: u64 = T::MinValue::get();
+
+		/// The largest value an account may store.
+		const MaxValue: u64 = T::MaxValue::get();
+
+		/// The number of most recent changes kept in `History`.
+		const MaxHistory: u32 = T::MaxHistory::get();
+
+		/// The amount reserved from an account when it first stores a value.
+		const StorageDeposit: BalanceOf<T> = T::StorageDeposit::get();
+
 		// Events must be initialized if they are used by the pallet.
 		fn deposit_event() = default;
 
+		fn integrity_test() {
+			assert!(
+				T::MinValue::get() <= T::MaxValue::get(),
+				"`MinValue` must not exceed `MaxValue`",
+			);
+		}
+
 		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]
-		pub fn do_something(origin, something: u32) -> dispatch::DispatchResult {
+		/// the caller's entry in storage and emits an event. This function must be dispatched by a
+		/// signed extrinsic, and the value must lie between `MinValue` and `MaxValue`.
+		///
+		/// The global `Something` value is also updated so that it always holds the value most
+		/// recently stored by any account.
+		///
+		/// The first time an account stores a value, `StorageDeposit` is reserved from it until the
+		/// value is cleared.
+		#[weight = T::WeightInfo::do_something()]
+		pub fn do_something(origin, something: u64) -> dispatch::DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
 			let who = ensure_signed(origin)?;
 
+			ensure!(Self::in_range(something), Error::<T>::ValueOutOfRange);
+
+			// Reserve the deposit for a new entry; this fails if the caller cannot afford it.
+			if !<SomethingOf<T>>::contains_key(&who) {
+				let deposit = T::StorageDeposit::get();
+				T::Currency::reserve(&who, deposit)?;
+				<Deposits<T>>::insert(&who, deposit);
+			}
+
 			// Update storage.
+			<SomethingOf<T>>::insert(&who, something);
 			Something::put(something);
+			Self::record(&who, Some(something));
 
 			// Emit an event.
 			Self::deposit_event(RawEvent::SomethingStored(something, who));
@@ -81,23 +280,128 @@ decl_module! {
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		/// An example dispatchable that may throw a custom error. It increments the value stored by
+		/// the caller, handling `MaxValue` according to `T::OverflowPolicy`.
+		#[weight = T::WeightInfo::cause_error()]
 		pub fn cause_error(origin) -> dispatch::DispatchResult {
-			let _who = ensure_signed(origin)?;
-
-			// Read a value from storage.
-			match Something::get() {
-				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue)?,
-				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					Something::put(new);
-					Ok(())
-				},
-			}
+			let who = ensure_signed(origin)?;
+
+			// Compute the incremented value; will error if unset or in the event of overflow.
+			let new = Self::next_something_of(&who)?;
+			// Update the value in storage with the incremented result.
+			<SomethingOf<T>>::insert(&who, new);
+			Self::record(&who, Some(new));
+			Ok(())
+		}
+
+		/// Remove the value stored by the caller, return its deposit and emit an event.
+		#[weight = T::WeightInfo::clear_something()]
+		pub fn clear_something(origin) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// Return an error if the caller has not stored a value.
+			ensure!(<SomethingOf<T>>::contains_key(&who), Error::<T>::NoneValue);
+
+			<SomethingOf<T>>::remove(&who);
+			Self::record(&who, None);
+			T::Currency::unreserve(&who, <Deposits<T>>::take(&who));
+
+			Self::deposit_event(RawEvent::SomethingCleared(who));
+			Ok(())
+		}
+
+		/// Set the value stored by `who`, as `do_something` would if `who` had called it, but
+		/// without reserving a deposit.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		#[weight = T::WeightInfo::force_set_something()]
+		pub fn force_set_something(
+			origin,
+			who: <T::Lookup as StaticLookup>::Source,
+			something: u64,
+		) -> dispatch::DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			ensure!(Self::in_range(something), Error::<T>::ValueOutOfRange);
+
+			<SomethingOf<T>>::insert(&who, something);
+			Something::put(something);
+			Self::record(&who, Some(something));
+
+			Self::deposit_event(RawEvent::SomethingForceSet(something, who));
+			Ok(())
+		}
+
+		/// Remove the value stored by `who` and return its deposit, if any.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		#[weight = T::WeightInfo::kill_something()]
+		pub fn kill_something(
+			origin,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			ensure!(<SomethingOf<T>>::contains_key(&who), Error::<T>::NoneValue);
+
+			<SomethingOf<T>>::remove(&who);
+			Self::record(&who, None);
+			T::Currency::unreserve(&who, <Deposits<T>>::take(&who));
+
+			Self::deposit_event(RawEvent::SomethingKilled(who));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Whether `value` lies between `MinValue` and `MaxValue`.
+	pub fn in_range(value: u64) -> bool {
+		T::MinValue::get() <= value && value <= T::MaxValue::get()
+	}
+
+	/// The value `cause_error` would store for `who`, or the error it would fail with.
+	pub fn next_something_of(who: &T::AccountId) -> Result<u64, Error<T>> {
+		// Read a value from storage.
+		match <SomethingOf<T>>::get(who) {
+			// Return an error if the value has not been set.
+			None => Err(Error::<T>::NoneValue),
+			// Increment the value read from storage according to the overflow policy; will error
+			// if the policy refuses to go past `MaxValue`.
+			Some(old) => T::OverflowPolicy::next(old, T::MinValue::get(), T::MaxValue::get())
+				.ok_or(Error::<T>::StorageOverflow),
+		}
+	}
+
+	/// The last `n` recorded changes, oldest first.
+	pub fn history(n: u32) -> Vec<HistoryEntry<T::AccountId, T::BlockNumber>> {
+		let end = HistoryEnd::get();
+		let start = HistoryStart::get().max(end.saturating_sub(n.into()));
+		(start..end).filter_map(<History<T>>::get).collect()
+	}
+
+	/// Record a change to the value of `who` in `History`, evicting up to
+	/// `MAX_EVICTIONS_PER_CHANGE` of the oldest entries beyond `MaxHistory`.
+	fn record(who: &T::AccountId, value: Option<u64>) {
+		let end = HistoryEnd::get();
+		<History<T>>::insert(end, HistoryEntry {
+			value,
+			who: who.clone(),
+			block_number: <frame_system::Module<T>>::block_number(),
+		});
+		let end = end + 1;
+		HistoryEnd::put(end);
+
+		// Usually evicts a single entry. If `MaxHistory` was lowered, the excess is evicted over
+		// the following changes rather than all at once.
+		let start = HistoryStart::get();
+		let excess = (end - start).saturating_sub(T::MaxHistory::get().into());
+		let evicted = excess.min(MAX_EVICTIONS_PER_CHANGE);
+		for index in start..start + evicted {
+			<History<T>>::remove(index);
 		}
+		HistoryStart::put(start + evicted);
 	}
 }
@@ -0,0 +1,87 @@
+//! Storage migrations for the template pallet.
+//!
+//! Every layout change bumps `StorageVersion` and adds a module here named after the version it
+//! migrates to. `migrate` runs whichever of them the on-chain version has not reached yet, in
+//! order.
+//!
+//! The pallet does not run them itself, as `v2` needs an account that only the runtime can name.
+//! Runtimes call `migrate` from an `OnRuntimeUpgrade` passed to `Executive`.
+
+use super::*;
+use frame_support::{weights::Weight, IterableStorageMap, StorageValue};
+
+/// Run all pending migrations and return the weight they consumed. `legacy_owner` is passed on to
+/// `v2::migrate`.
+pub fn migrate<T: Trait>(legacy_owner: &T::AccountId) -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if StorageVersion::get() == Releases::V1_0_0 {
+		#[cfg(feature = "try-runtime")]
+		let entries = v2::pre_upgrade::<T>().expect("pre_upgrade checks for v2 failed");
+
+		weight = weight.saturating_add(v2::migrate::<T>(legacy_owner));
+
+		#[cfg(feature = "try-runtime")]
+		v2::post_upgrade::<T>(entries).expect("post_upgrade checks for v2 failed");
+	}
+
+	weight
+}
+
+/// Widen `Something` and `SomethingOf` from `u32` to `u64`, and give a global value stored before
+/// values were kept per account to a given account.
+pub mod v2 {
+	use super::*;
+
+	/// Re-encode every stored value as `u64`, move a global value that has no per-account values
+	/// alongside it into `SomethingOf` and set the storage version to `V2_0_0`.
+	///
+	/// Such a value was stored by a chain that predates `SomethingOf`, which does not record who
+	/// stored it, so it goes to `legacy_owner` without a deposit. `Something` keeps its value as
+	/// the one most recently stored.
+	pub fn migrate<T: Trait>(legacy_owner: &T::AccountId) -> Weight {
+		let _ = Something::translate::<u32, _>(|old| old.map(u64::from));
+		<SomethingOf<T>>::translate::<u32, _>(|_, old| Some(u64::from(old)));
+
+		let mut entries = <SomethingOf<T>>::iter().count() as Weight;
+		let mut legacy: Weight = 0;
+		if entries == 0 {
+			if let Some(something) = Something::get() {
+				<SomethingOf<T>>::insert(legacy_owner, something);
+				entries = 1;
+				legacy = 1;
+			}
+		}
+		StorageVersion::put(Releases::V2_0_0);
+
+		T::DbWeight::get().reads_writes(entries + 1, entries + 2 + legacy)
+	}
+
+	/// Check that the pallet is still on the old layout and return the number of `SomethingOf`
+	/// entries there should be once migrated: those that decode as `u32`, or one for the legacy
+	/// owner if there are none but a global value is set.
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_upgrade<T: Trait>() -> Result<usize, &'static str> {
+		use frame_support::storage::migration::{get_storage_value, StorageIterator};
+
+		ensure!(StorageVersion::get() == Releases::V1_0_0, "storage version is not V1_0_0");
+		let entries = StorageIterator::<u32>::new(b"TemplateModule", b"SomethingOf").count();
+		let global = get_storage_value::<u32>(b"TemplateModule", b"Something", &[]);
+		if entries == 0 && global.is_some() {
+			return Ok(1);
+		}
+		Ok(entries)
+	}
+
+	/// Check that the storage version was bumped and that all `entries` values counted by
+	/// `pre_upgrade` are present and decode as `u64`.
+	#[cfg(feature = "try-runtime")]
+	pub fn post_upgrade<T: Trait>(entries: usize) -> Result<(), &'static str> {
+		ensure!(StorageVersion::get() == Releases::V2_0_0, "storage version is not V2_0_0");
+		ensure!(
+			<SomethingOf<T>>::iter().count() == entries,
+			"some `SomethingOf` entries were lost or do not decode as u64"
+		);
+		Ok(())
+	}
+}
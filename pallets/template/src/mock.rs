@@ -1,6 +1,13 @@
-use crate::{Module, Trait};
+use crate::{
+	Module, Trait, GenesisConfig, Something, SomethingOf,
+	OverflowPolicy, FailOnOverflow, SaturateOnOverflow, WrapOnOverflow,
+};
+use std::cell::RefCell;
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{
+	impl_outer_event, impl_outer_origin, parameter_types, storage::unhashed, traits::Get,
+	weights::Weight, StorageMap, StorageValue,
+};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
@@ -10,6 +17,18 @@ impl_outer_origin! {
 	pub enum Origin for Test {}
 }
 
+mod template {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_balances<T>,
+		template<T>,
+	}
+}
+
 // Configure a mock runtime to test the pallet.
 
 #[derive(Clone, Eq, PartialEq)]
@@ -19,6 +38,8 @@ parameter_types! {
 	pub const MaximumBlockWeight: Weight = 1024;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const ExistentialDeposit: u64 = 1;
+	pub const StorageDeposit: u64 = 10;
 }
 
 impl system::Trait for Test {
@@ -32,7 +53,7 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = TestEvent;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type DbWeight = ();
@@ -43,19 +64,142 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type PalletInfo = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 }
 
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static MIN_VALUE: RefCell<u64> = RefCell::new(0);
+	static MAX_VALUE: RefCell<u64> = RefCell::new(u64::max_value());
+	static POLICY: RefCell<Policy> = RefCell::new(Policy::Fail);
+	static MAX_HISTORY: RefCell<u32> = RefCell::new(3);
+}
+
+pub struct MinValue;
+impl Get<u64> for MinValue {
+	fn get() -> u64 {
+		MIN_VALUE.with(|v| *v.borrow())
+	}
+}
+
+pub struct MaxValue;
+impl Get<u64> for MaxValue {
+	fn get() -> u64 {
+		MAX_VALUE.with(|v| *v.borrow())
+	}
+}
+
+pub struct MaxHistory;
+impl Get<u32> for MaxHistory {
+	fn get() -> u32 {
+		MAX_HISTORY.with(|v| *v.borrow())
+	}
+}
+
+/// The overflow policy `TestOverflowPolicy` delegates to.
+#[derive(Clone, Copy)]
+pub enum Policy {
+	Fail,
+	Saturate,
+	Wrap,
+}
+
+pub struct TestOverflowPolicy;
+impl OverflowPolicy for TestOverflowPolicy {
+	fn next(value: u64, min: u64, max: u64) -> Option<u64> {
+		match POLICY.with(|p| *p.borrow()) {
+			Policy::Fail => FailOnOverflow::next(value, min, max),
+			Policy::Saturate => SaturateOnOverflow::next(value, min, max),
+			Policy::Wrap => WrapOnOverflow::next(value, min, max),
+		}
+	}
+}
+
+// Set the bounds and overflow policy used by the mock runtime in the current test.
+pub fn set_bounds(min: u64, max: u64, policy: Policy) {
+	MIN_VALUE.with(|v| *v.borrow_mut() = min);
+	MAX_VALUE.with(|v| *v.borrow_mut() = max);
+	POLICY.with(|p| *p.borrow_mut() = policy);
+}
+
+// Set the number of history entries kept by the mock runtime in the current test.
+pub fn set_max_history(max: u32) {
+	MAX_HISTORY.with(|v| *v.borrow_mut() = max);
+}
+
 impl Trait for Test {
-	type Event = ();
+	type Event = TestEvent;
+	type MinValue = MinValue;
+	type MaxValue = MaxValue;
+	type OverflowPolicy = TestOverflowPolicy;
+	type ForceOrigin = system::EnsureRoot<u64>;
+	type MaxHistory = MaxHistory;
+	type Currency = Balances;
+	type StorageDeposit = StorageDeposit;
+	type WeightInfo = ();
 }
 
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
 pub type TemplateModule = Module<Test>;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	new_test_ext_with(GenesisConfig::default())
+}
+
+// Build genesis storage with the given template pallet configuration. Accounts 1 to 3 are
+// endowed with 100 units each; other accounts have nothing.
+pub fn new_test_ext_with(config: GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	config.assimilate_storage(&mut t).unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	// Events are not deposited in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+// The last event deposited by the template pallet.
+pub fn last_event() -> crate::RawEvent<u64> {
+	System::events().into_iter()
+		.filter_map(|record| match record.event {
+			TestEvent::template(event) => Some(event),
+			_ => None,
+		})
+		.last()
+		.expect("the template pallet deposited an event")
+}
+
+// Build storage as a chain that predates `StorageVersion` would have it: no storage version and
+// `u32` values.
+pub fn new_test_ext_v1(
+	something: Option<u32>,
+	something_of: &[(u64, u32)],
+) -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| {
+		if let Some(value) = something {
+			unhashed::put(&Something::hashed_key(), &value);
+		}
+		for (who, value) in something_of {
+			unhashed::put(&<SomethingOf<Test>>::hashed_key_for(who), value);
+		}
+	});
+	ext
 }
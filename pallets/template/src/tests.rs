@@ -1,5 +1,12 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{
+	Error, GenesisConfig, HistoryEntry, RawEvent, Releases, StorageVersion, migrations, mock::*,
+};
+use frame_support::{
+	assert_ok, assert_noop,
+	traits::{Currency, IntegrityTest, ReservableCurrency},
+	StorageValue,
+};
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn it_works_for_default_value() {
@@ -8,6 +15,7 @@ fn it_works_for_default_value() {
 		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
 		// Read pallet storage and assert an expected result.
 		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(TemplateModule::something_of(1), Some(42));
 	});
 }
 
@@ -21,3 +29,445 @@ fn correct_error_for_none_value() {
 		);
 	});
 }
+
+#[test]
+fn values_are_stored_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 7));
+		// Each account keeps its own value.
+		assert_eq!(TemplateModule::something_of(1), Some(42));
+		assert_eq!(TemplateModule::something_of(2), Some(7));
+		assert_eq!(TemplateModule::something_of(3), None);
+		// The global value tracks the latest write.
+		assert_eq!(TemplateModule::something(), Some(7));
+	});
+}
+
+#[test]
+fn cause_error_increments_only_the_callers_value() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 7));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(43));
+		assert_eq!(TemplateModule::something_of(2), Some(7));
+		// Account 3 never stored anything.
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(3)),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn correct_error_for_overflow() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), u64::max_value()));
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(1)),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+#[test]
+fn clear_something_removes_only_the_callers_value() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 7));
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), None);
+		assert_eq!(TemplateModule::something_of(2), Some(7));
+		// Clearing twice fails.
+		assert_noop!(
+			TemplateModule::clear_something(Origin::signed(1)),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+// The account the runtime would give a lone global value to when migrating to v2.
+const LEGACY_OWNER: u64 = 99;
+
+#[test]
+fn genesis_sets_latest_storage_version() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		// Nothing to migrate on a fresh chain.
+		migrations::migrate::<Test>(&LEGACY_OWNER);
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+	});
+}
+
+#[test]
+fn migrate_to_v2_widens_stored_values() {
+	new_test_ext_v1(Some(7), &[(1, 42), (2, u32::max_value())]).execute_with(|| {
+		assert_eq!(StorageVersion::get(), Releases::V1_0_0);
+
+		migrations::migrate::<Test>(&LEGACY_OWNER);
+
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		assert_eq!(TemplateModule::something(), Some(7));
+		assert_eq!(TemplateModule::something_of(1), Some(42));
+		assert_eq!(TemplateModule::something_of(2), Some(u32::max_value() as u64));
+		assert_eq!(TemplateModule::something_of(3), None);
+
+		// Values above the old bound can now be reached.
+		assert_ok!(TemplateModule::cause_error(Origin::signed(2)));
+		assert_eq!(TemplateModule::something_of(2), Some(u32::max_value() as u64 + 1));
+	});
+}
+
+#[test]
+fn migrate_to_v2_gives_a_lone_global_value_to_the_legacy_owner() {
+	new_test_ext_v1(Some(7), &[]).execute_with(|| {
+		migrations::migrate::<Test>(&LEGACY_OWNER);
+
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		assert_eq!(TemplateModule::something(), Some(7));
+		assert_eq!(TemplateModule::something_of(LEGACY_OWNER), Some(7));
+		assert_eq!(TemplateModule::deposit_of(LEGACY_OWNER), 0);
+	});
+}
+
+#[test]
+fn migrate_to_v2_handles_empty_storage() {
+	new_test_ext_v1(None, &[]).execute_with(|| {
+		migrations::migrate::<Test>(&LEGACY_OWNER);
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		assert_eq!(TemplateModule::something(), None);
+	});
+}
+
+#[test]
+fn genesis_config_seeds_values() {
+	new_test_ext_with(GenesisConfig {
+		something: Some(7),
+		something_of: vec![(1, 42), (2, 3)],
+	}).execute_with(|| {
+		assert_eq!(TemplateModule::something(), Some(7));
+		assert_eq!(TemplateModule::something_of(1), Some(42));
+		assert_eq!(TemplateModule::something_of(2), Some(3));
+		assert_eq!(TemplateModule::something_of(3), None);
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		// Seeded values behave like any other stored value.
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(43));
+	});
+}
+
+#[test]
+fn default_genesis_config_stores_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::something(), None);
+		assert_eq!(TemplateModule::something_of(1), None);
+	});
+}
+
+#[test]
+fn next_something_of_mirrors_cause_error() {
+	new_test_ext().execute_with(|| {
+		assert!(matches!(TemplateModule::next_something_of(&1), Err(Error::<Test>::NoneValue)));
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(TemplateModule::next_something_of(&1).ok(), Some(43));
+		// The query does not change storage.
+		assert_eq!(TemplateModule::something_of(1), Some(42));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), u64::max_value()));
+		assert!(matches!(
+			TemplateModule::next_something_of(&2),
+			Err(Error::<Test>::StorageOverflow)
+		));
+	});
+}
+
+#[test]
+fn do_something_rejects_values_out_of_range() {
+	new_test_ext().execute_with(|| {
+		set_bounds(10, 100, Policy::Fail);
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(1), 9),
+			Error::<Test>::ValueOutOfRange
+		);
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(1), 101),
+			Error::<Test>::ValueOutOfRange
+		);
+		// The bounds are inclusive.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 100));
+	});
+}
+
+#[test]
+#[should_panic(expected = "`MinValue` must not exceed `MaxValue`")]
+fn integrity_test_rejects_inverted_bounds() {
+	set_bounds(10, 9, Policy::Fail);
+	<TemplateModule as IntegrityTest>::integrity_test();
+}
+
+#[test]
+fn fail_policy_errors_at_max_value() {
+	new_test_ext().execute_with(|| {
+		set_bounds(10, 100, Policy::Fail);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 99));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(100));
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(1)),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+#[test]
+fn saturate_policy_stays_at_max_value() {
+	new_test_ext().execute_with(|| {
+		set_bounds(10, 100, Policy::Saturate);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 99));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(100));
+
+		set_bounds(0, u64::max_value(), Policy::Saturate);
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), u64::max_value()));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(2)));
+		assert_eq!(TemplateModule::something_of(2), Some(u64::max_value()));
+	});
+}
+
+#[test]
+fn wrap_policy_restarts_from_min_value() {
+	new_test_ext().execute_with(|| {
+		set_bounds(10, 100, Policy::Wrap);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 99));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(100));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(10));
+
+		set_bounds(0, u64::max_value(), Policy::Wrap);
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), u64::max_value()));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(2)));
+		assert_eq!(TemplateModule::something_of(2), Some(0));
+	});
+}
+
+#[test]
+fn wrap_policy_moves_values_below_min_value_to_it() {
+	new_test_ext().execute_with(|| {
+		set_bounds(0, 100, Policy::Wrap);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 5));
+
+		// `MinValue` was raised after the value was stored.
+		set_bounds(10, 100, Policy::Wrap);
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(10));
+	});
+}
+
+#[test]
+fn user_calls_deposit_user_events() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(last_event(), RawEvent::SomethingStored(42, 1));
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_eq!(last_event(), RawEvent::SomethingCleared(1));
+	});
+}
+
+#[test]
+fn force_set_something_sets_any_accounts_value() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::force_set_something(Origin::root(), 1, 7));
+		assert_eq!(TemplateModule::something_of(1), Some(7));
+		assert_eq!(TemplateModule::something(), Some(7));
+		assert_eq!(last_event(), RawEvent::SomethingForceSet(7, 1));
+
+		set_bounds(0, 100, Policy::Fail);
+		assert_noop!(
+			TemplateModule::force_set_something(Origin::root(), 1, 101),
+			Error::<Test>::ValueOutOfRange
+		);
+	});
+}
+
+#[test]
+fn kill_something_removes_any_accounts_value() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::kill_something(Origin::root(), 1));
+		assert_eq!(TemplateModule::something_of(1), None);
+		assert_eq!(last_event(), RawEvent::SomethingKilled(1));
+		assert_noop!(
+			TemplateModule::kill_something(Origin::root(), 1),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn force_calls_require_force_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_noop!(TemplateModule::force_set_something(Origin::signed(1), 1, 7), BadOrigin);
+		assert_noop!(TemplateModule::force_set_something(Origin::none(), 1, 7), BadOrigin);
+		assert_noop!(TemplateModule::kill_something(Origin::signed(1), 1), BadOrigin);
+		assert_noop!(TemplateModule::kill_something(Origin::none(), 1), BadOrigin);
+	});
+}
+
+fn entry(value: Option<u64>, who: u64, block_number: u64) -> HistoryEntry<u64, u64> {
+	HistoryEntry { value, who, block_number }
+}
+
+#[test]
+fn history_records_every_change() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::history(10), vec![]);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_eq!(TemplateModule::history(10), vec![
+			entry(Some(42), 1, 1),
+			entry(Some(43), 1, 2),
+			entry(None, 1, 2),
+		]);
+		// Failed calls record nothing.
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(1)),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn history_returns_the_last_n_entries() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 1));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 2));
+		assert_ok!(TemplateModule::force_set_something(Origin::root(), 3, 3));
+		assert_eq!(TemplateModule::history(2), vec![
+			entry(Some(2), 2, 1),
+			entry(Some(3), 3, 1),
+		]);
+		assert_eq!(TemplateModule::history(0), vec![]);
+	});
+}
+
+#[test]
+fn history_evicts_the_oldest_entry_on_wraparound() {
+	new_test_ext().execute_with(|| {
+		// `MaxHistory` is 3 in the mock.
+		for value in 1..=5 {
+			System::set_block_number(value);
+			assert_ok!(TemplateModule::do_something(Origin::signed(1), value));
+		}
+		assert_eq!(TemplateModule::history(10), vec![
+			entry(Some(3), 1, 3),
+			entry(Some(4), 1, 4),
+			entry(Some(5), 1, 5),
+		]);
+		assert_eq!(TemplateModule::history_start(), 2);
+		assert_eq!(TemplateModule::history_end(), 5);
+		// Evicted entries are removed from storage.
+		assert_eq!(TemplateModule::history_entry(0), None);
+		assert_eq!(TemplateModule::history_entry(1), None);
+
+		assert_ok!(TemplateModule::kill_something(Origin::root(), 1));
+		assert_eq!(TemplateModule::history(10), vec![
+			entry(Some(4), 1, 4),
+			entry(Some(5), 1, 5),
+			entry(None, 1, 5),
+		]);
+	});
+}
+
+#[test]
+fn lowering_max_history_evicts_the_excess_over_several_changes() {
+	new_test_ext().execute_with(|| {
+		set_max_history(6);
+		for value in 1..=6 {
+			assert_ok!(TemplateModule::do_something(Origin::signed(1), value));
+		}
+
+		set_max_history(3);
+		// Each change evicts at most two entries, so it takes three changes to get back down to
+		// three entries.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 7));
+		assert_eq!(TemplateModule::history_start(), 2);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 8));
+		assert_eq!(TemplateModule::history_start(), 4);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 9));
+		assert_eq!(TemplateModule::history_start(), 6);
+		assert_eq!(TemplateModule::history(10).len(), 3);
+		assert_eq!(TemplateModule::history_entry(5), None);
+
+		// From then on, one entry is evicted per change.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_eq!(TemplateModule::history_start(), 7);
+		assert_eq!(TemplateModule::history(10).len(), 3);
+	});
+}
+
+#[test]
+fn first_write_reserves_the_storage_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(TemplateModule::deposit_of(1), 10);
+		// Overwriting or incrementing the value reserves nothing more.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 7));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 10);
+	});
+}
+
+#[test]
+fn clearing_returns_the_storage_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(TemplateModule::deposit_of(1), 0);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 42));
+		assert_ok!(TemplateModule::kill_something(Origin::root(), 2));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn forced_values_carry_no_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::reserve(&1, 5));
+		assert_ok!(TemplateModule::force_set_something(Origin::root(), 1, 42));
+		assert_eq!(TemplateModule::deposit_of(1), 0);
+		// Clearing a forced value leaves unrelated reserves alone.
+		assert_ok!(TemplateModule::clear_something(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 5);
+	});
+}
+
+#[test]
+fn correct_error_for_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		// Account 4 has no funds.
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(4), 42),
+			pallet_balances::Error::<Test, pallet_balances::DefaultInstance>::InsufficientBalance
+		);
+		// Account 5 cannot cover the deposit of 10.
+		Balances::make_free_balance_be(&5, 5);
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(5), 42),
+			pallet_balances::Error::<Test, pallet_balances::DefaultInstance>::InsufficientBalance
+		);
+		assert_eq!(TemplateModule::something_of(5), None);
+	});
+}
@@ -0,0 +1,17 @@
+//! Runtime API definition for the validator set pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// Build what `report_aura_equivocation` needs besides the headers.
+	pub trait ValidatorSetApi<AuthorityId, KeyOwnerProof> where
+		AuthorityId: Codec,
+		KeyOwnerProof: Codec,
+	{
+		/// A proof that `authority_id` is the Aura key of a validator in the current session, or
+		/// `None` if it is not.
+		fn generate_aura_key_ownership_proof(authority_id: AuthorityId) -> Option<KeyOwnerProof>;
+	}
+}
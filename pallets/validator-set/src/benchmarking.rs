@@ -0,0 +1,59 @@
+//! Benchmarking setup for pallet-validator-set
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_support::StorageValue;
+use sp_runtime::traits::StaticLookup;
+
+const SEED: u32 = 0;
+
+// Fill the validator set with `count` accounts.
+fn set_validators<T: Trait>(count: u32) -> Vec<T::AccountId> {
+	let validators: Vec<T::AccountId> =
+		(0..count).map(|i| account("validator", i, SEED)).collect();
+	<Validators<T>>::put(&validators);
+	validators
+}
+
+benchmarks! {
+	_ { }
+
+	add_validator {
+		let v in 1 .. T::MaxValidators::get() - 1;
+		let origin = T::AddRemoveOrigin::successful_origin();
+		set_validators::<T>(v);
+		let validator: T::AccountId = account("new", 0, SEED);
+		let source = T::Lookup::unlookup(validator.clone());
+	}: _<T::Origin>(origin, source)
+	verify {
+		assert!(Module::<T>::validators().contains(&validator));
+	}
+
+	remove_validator {
+		let v in 2 .. T::MaxValidators::get();
+		let origin = T::AddRemoveOrigin::successful_origin();
+		let validators = set_validators::<T>(v);
+		// The last validator is the most expensive to find.
+		let validator = validators[v as usize - 1].clone();
+		let source = T::Lookup::unlookup(validator.clone());
+	}: _<T::Origin>(origin, source)
+	verify {
+		assert!(!Module::<T>::validators().contains(&validator));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_validator::<Test>());
+			assert_ok!(test_benchmark_remove_validator::<Test>());
+		});
+	}
+}
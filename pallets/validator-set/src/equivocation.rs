@@ -0,0 +1,118 @@
+//! Aura equivocations: an authority sealing two different blocks for the same slot.
+//!
+//! Aura in this Substrate version does not report equivocations itself, so anyone who sees two
+//! such headers may report them with `report_aura_equivocation`. Both seals are checked on chain
+//! against the offender's Aura key, and a proof from `T::KeyOwnerProofSystem` ties that key to a
+//! validator of some session. The resulting `AuraEquivocationOffence` goes to `T::ReportOffence`,
+//! which in a runtime with `pallet_offences` hands it back to this pallet's offence handler.
+//!
+//! The slot is not tied to the session of the key ownership proof: a proof from another session in
+//! which the offender held the same key is accepted as well. Reports are deduplicated by slot and
+//! offender, so this does not let an equivocation be reported twice.
+
+use super::*;
+use codec::{Decode, Encode};
+use frame_support::weights::constants::{WEIGHT_PER_MICROS, WEIGHT_PER_NANOS};
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_runtime::{PerThing, RuntimeAppPublic, RuntimeDebug, traits::Header as HeaderT};
+use sp_staking::offence::{Kind, Offence};
+
+/// The Aura key type of a runtime.
+pub type AuraIdOf<T> = <T as pallet_aura::Trait>::AuthorityId;
+
+/// Two different headers that an Aura authority sealed for the same slot.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
+pub struct AuraEquivocationProof<Header, AuthorityId> {
+	/// The Aura key that sealed both headers.
+	pub offender: AuthorityId,
+	/// The slot both headers were authored in.
+	pub slot: u64,
+	/// The first header, with its seal.
+	pub first_header: Header,
+	/// The second header, with its seal.
+	pub second_header: Header,
+}
+
+/// An Aura authority sealed two different blocks for the same slot.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AuraEquivocationOffence<FullIdentification> {
+	/// The slot in which the blocks were authored.
+	pub slot: u64,
+	/// The session of the offender's key ownership proof.
+	pub session_index: SessionIndex,
+	/// The size of the validator set in that session.
+	pub validator_set_count: u32,
+	/// The validator that sealed both blocks.
+	pub offender: FullIdentification,
+}
+
+impl<FullIdentification: Clone> Offence<FullIdentification>
+	for AuraEquivocationOffence<FullIdentification>
+{
+	const ID: Kind = *b"aura:equivocatio";
+	type TimeSlot = u64;
+
+	fn offenders(&self) -> Vec<FullIdentification> {
+		vec![self.offender.clone()]
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		self.validator_set_count
+	}
+
+	fn time_slot(&self) -> Self::TimeSlot {
+		self.slot
+	}
+
+	// The fraction GRANDPA and BABE equivocations use. Nothing is slashed by this pallet.
+	fn slash_fraction(offenders_count: u32, validator_set_count: u32) -> Perbill {
+		let x = Perbill::from_rational_approximation(3 * offenders_count, validator_set_count);
+		x.square()
+	}
+}
+
+/// The slot of `header`, if its Aura pre-runtime digest names one and `offender` sealed it.
+///
+/// The seal must be the last digest item, signing the hash of the header without it, as the Aura
+/// client seals blocks.
+pub fn sealed_slot<T: Trait>(header: &T::Header, offender: &AuraIdOf<T>) -> Option<u64> {
+	let mut header = header.clone();
+	let seal = header.digest_mut().pop()?;
+	let signature = match seal.as_seal() {
+		Some((id, mut signature)) if id == AURA_ENGINE_ID =>
+			<AuraIdOf<T> as RuntimeAppPublic>::Signature::decode(&mut signature).ok()?,
+		_ => return None,
+	};
+	if !offender.verify(&header.hash(), &signature) {
+		return None;
+	}
+
+	header.digest().logs().iter()
+		.filter_map(|log| log.as_pre_runtime())
+		.find_map(|(id, mut slot)| if id == AURA_ENGINE_ID {
+			u64::decode(&mut slot).ok()
+		} else {
+			None
+		})
+}
+
+/// The weight of `report_aura_equivocation` with a key ownership proof for a set of
+/// `validator_count` validators.
+///
+/// Like `pallet_grandpa`'s `report_equivocation`, the call is not benchmarked; the weight is
+/// estimated from the work it does.
+pub fn report_aura_equivocation_weight<T: Trait>(validator_count: u32) -> Weight {
+	// Checking the key ownership proof, for at least 100 validators.
+	(35 * WEIGHT_PER_MICROS)
+		.saturating_add((175 * WEIGHT_PER_NANOS).saturating_mul(validator_count.max(100).into()))
+		.saturating_add(T::DbWeight::get().reads(5))
+		// Hashing both headers and checking both seals.
+		.saturating_add(100 * WEIGHT_PER_MICROS)
+		// Reporting the offence and removing the offender.
+		.saturating_add(110 * WEIGHT_PER_MICROS)
+		.saturating_add(T::DbWeight::get().reads_writes(8, 8))
+}
@@ -0,0 +1,329 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Validator Set Pallet
+//!
+//! Keeps the set of validators that `pallet_session` rotates in, so that authorities can be added
+//! and removed without a hard fork.
+//!
+//! The set starts from the genesis config and changes through `add_validator` and
+//! `remove_validator`, dispatched by `T::AddRemoveOrigin`. A change is handed to `pallet_session`
+//! when it plans the next session and, like every change it plans, takes effect one session after
+//! that. A validator added to the set must register its session keys with `Session::set_keys`
+//! before it comes into effect.
+//!
+//! The pallet also serves as the inner session manager of `pallet_session::historical`, which
+//! keeps the roots of past validator sets so that key ownership can be proven in offence reports.
+//!
+//! As the runtime's offence handler, the pallet removes validators reported for an offence from the
+//! set if `T::RemoveOffenders` is set, as `remove_validator` would, so that they leave after the
+//! next session. The last validator is never removed. Offenders are not disabled for the rest of
+//! the session: `pallet_session` would only pass that on to Aura and GRANDPA as `OnDisabled`
+//! consensus logs, which their clients in this Substrate version ignore.
+//!
+//! Offences reach the handler from the pallets that report them. GRANDPA reports equivocations
+//! itself. Aura does not, so this pallet takes reports of Aura equivocations with
+//! `report_aura_equivocation`, checks them and passes them on to `T::ReportOffence`; see the
+//! `equivocation` module.
+
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, Parameter,
+	traits::{EnsureOrigin, Get, KeyOwnerProofSystem},
+	weights::Weight,
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+	KeyTypeId, Perbill, RuntimeAppPublic,
+	traits::{Convert, Header as HeaderT, StaticLookup},
+};
+use sp_session::{GetSessionNumber, GetValidatorCount};
+use sp_staking::{SessionIndex, offence::{OffenceDetails, OnOffenceHandler, ReportOffence}};
+use sp_std::prelude::*;
+
+pub mod equivocation;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub use equivocation::{AuraEquivocationOffence, AuraEquivocationProof, AuraIdOf};
+pub use weights::WeightInfo;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait:
+	pallet_session::Trait<ValidatorId = <Self as frame_system::Trait>::AccountId> +
+	pallet_aura::Trait
+{
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The origin allowed to add and remove validators.
+	type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The largest number of validators the set may hold.
+	type MaxValidators: Get<u32>;
+
+	/// Whether validators reported for an offence are also removed from the set.
+	type RemoveOffenders: Get<bool>;
+
+	/// Proves that an Aura key belonged to a validator in a given session, usually
+	/// `pallet_session::historical`.
+	type KeyOwnerProofSystem: KeyOwnerProofSystem<
+		(KeyTypeId, AuraIdOf<Self>),
+		Proof = Self::KeyOwnerProof,
+		IdentificationTuple = Self::KeyOwnerIdentification,
+	>;
+
+	/// A proof that an Aura key belonged to a validator in a given session.
+	type KeyOwnerProof: Parameter + GetSessionNumber + GetValidatorCount;
+
+	/// The identification of the validator a key ownership proof resolves to.
+	type KeyOwnerIdentification: Parameter;
+
+	/// Where Aura equivocations are reported, usually `pallet_offences`.
+	type ReportOffence: ReportOffence<
+		Self::AccountId,
+		Self::KeyOwnerIdentification,
+		AuraEquivocationOffence<Self::KeyOwnerIdentification>,
+	>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as ValidatorSet {
+		/// The validators handed to `pallet_session` when it plans a session.
+		Validators get(fn validators) config(): Vec<T::AccountId>;
+
+		/// Whether `Validators` changed since `pallet_session` last planned a session.
+		ValidatorsChanged build(|config: &GenesisConfig<T>| !config.validators.is_empty()): bool;
+	}
+	add_extra_genesis {
+		build(|config: &GenesisConfig<T>| {
+			assert!(
+				config.validators.len() as u32 <= T::MaxValidators::get(),
+				"Genesis validators must not exceed `MaxValidators`",
+			);
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
+		/// A validator was added to the set. It takes part from the session after next. [who]
+		ValidatorAdded(AccountId),
+		/// A validator was removed from the set. It leaves after the next session. [who]
+		ValidatorRemoved(AccountId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The account is already in the validator set.
+		AlreadyValidator,
+		/// The account is not in the validator set.
+		NotValidator,
+		/// The validator set already holds `MaxValidators` validators.
+		TooManyValidators,
+		/// Removing the last validator would stop block production.
+		TooFewValidators,
+		/// The headers are the same, or not both sealed by the offender for the given slot.
+		InvalidEquivocationProof,
+		/// The key ownership proof does not prove that the offender was a validator.
+		InvalidKeyOwnershipProof,
+		/// The equivocation has already been reported.
+		DuplicateOffenceReport,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The largest number of validators the set may hold.
+		const MaxValidators: u32 = T::MaxValidators::get();
+
+		fn deposit_event() = default;
+
+		/// Add `validator` to the set from the session after next.
+		///
+		/// The dispatch origin for this call must be `T::AddRemoveOrigin`.
+		#[weight = T::WeightInfo::add_validator(T::MaxValidators::get())]
+		pub fn add_validator(
+			origin,
+			validator: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			let validator = T::Lookup::lookup(validator)?;
+
+			let mut validators = Self::validators();
+			ensure!(!validators.contains(&validator), Error::<T>::AlreadyValidator);
+			ensure!(
+				(validators.len() as u32) < T::MaxValidators::get(),
+				Error::<T>::TooManyValidators,
+			);
+
+			validators.push(validator.clone());
+			<Validators<T>>::put(validators);
+			ValidatorsChanged::put(true);
+
+			Self::deposit_event(RawEvent::ValidatorAdded(validator));
+			Ok(())
+		}
+
+		/// Remove `validator` from the set after the next session.
+		///
+		/// The dispatch origin for this call must be `T::AddRemoveOrigin`.
+		#[weight = T::WeightInfo::remove_validator(T::MaxValidators::get())]
+		pub fn remove_validator(
+			origin,
+			validator: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			let validator = T::Lookup::lookup(validator)?;
+
+			let mut validators = Self::validators();
+			let index = validators.iter().position(|v| *v == validator)
+				.ok_or(Error::<T>::NotValidator)?;
+			ensure!(validators.len() > 1, Error::<T>::TooFewValidators);
+
+			validators.remove(index);
+			<Validators<T>>::put(validators);
+			ValidatorsChanged::put(true);
+
+			Self::deposit_event(RawEvent::ValidatorRemoved(validator));
+			Ok(())
+		}
+
+		/// Report that an Aura authority sealed two different blocks for the same slot, as an
+		/// `AuraEquivocationOffence` to `T::ReportOffence`.
+		///
+		/// `key_owner_proof` must prove that the offender's Aura key belonged to a validator.
+		/// Anyone may report an equivocation, once.
+		#[weight = equivocation::report_aura_equivocation_weight::<T>(
+			key_owner_proof.validator_count(),
+		)]
+		pub fn report_aura_equivocation(
+			origin,
+			equivocation_proof: Box<AuraEquivocationProof<T::Header, AuraIdOf<T>>>,
+			key_owner_proof: T::KeyOwnerProof,
+		) -> dispatch::DispatchResult {
+			let reporter = ensure_signed(origin)?;
+			let AuraEquivocationProof { offender, slot, first_header, second_header } =
+				*equivocation_proof;
+
+			ensure!(
+				first_header.hash() != second_header.hash(),
+				Error::<T>::InvalidEquivocationProof,
+			);
+			for header in &[first_header, second_header] {
+				ensure!(
+					equivocation::sealed_slot::<T>(header, &offender) == Some(slot),
+					Error::<T>::InvalidEquivocationProof,
+				);
+			}
+
+			let session_index = key_owner_proof.session();
+			let validator_set_count = key_owner_proof.validator_count();
+			let key = (<AuraIdOf<T> as RuntimeAppPublic>::ID, offender);
+			let offender = T::KeyOwnerProofSystem::check_proof(key, key_owner_proof)
+				.ok_or(Error::<T>::InvalidKeyOwnershipProof)?;
+
+			let offence = AuraEquivocationOffence {
+				slot,
+				session_index,
+				validator_set_count,
+				offender,
+			};
+			T::ReportOffence::report_offence(vec![reporter], offence)
+				.map_err(|_| Error::<T>::DuplicateOffenceReport)?;
+			Ok(())
+		}
+	}
+}
+
+/// Plans each session with the current validator set, or keeps the previous one if the set has
+/// not changed.
+impl<T: Trait> pallet_session::SessionManager<T::AccountId> for Module<T> {
+	fn new_session(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		if ValidatorsChanged::take() {
+			Some(Self::validators())
+		} else {
+			None
+		}
+	}
+
+	fn end_session(_end_index: SessionIndex) {}
+
+	fn start_session(_start_index: SessionIndex) {}
+}
+
+/// Validators in this set are identified by their account alone; there is no stake or exposure to
+/// record alongside it in `pallet_session::historical`.
+pub struct FullIdentificationOf;
+
+impl<AccountId> Convert<AccountId, Option<()>> for FullIdentificationOf {
+	fn convert(_validator: AccountId) -> Option<()> {
+		Some(())
+	}
+}
+
+/// Plans sessions for `pallet_session::historical::NoteHistoricalRoot`, with `()` as each
+/// validator's full identification.
+impl<T: Trait> pallet_session::historical::SessionManager<T::AccountId, ()> for Module<T> {
+	fn new_session(new_index: SessionIndex) -> Option<Vec<(T::AccountId, ())>> {
+		<Self as pallet_session::SessionManager<_>>::new_session(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v, ())).collect())
+	}
+
+	fn end_session(_end_index: SessionIndex) {}
+
+	fn start_session(_start_index: SessionIndex) {}
+}
+
+/// Removes validators reported for an offence from the set if `T::RemoveOffenders` is set.
+impl<T: Trait, FullIdentification>
+	OnOffenceHandler<T::AccountId, (T::AccountId, FullIdentification), Weight> for Module<T>
+{
+	fn on_offence(
+		offenders: &[OffenceDetails<T::AccountId, (T::AccountId, FullIdentification)>],
+		_slash_fraction: &[Perbill],
+		_session: SessionIndex,
+	) -> Result<Weight, ()> {
+		if !T::RemoveOffenders::get() {
+			return Ok(0);
+		}
+
+		let mut validators = Self::validators();
+		let mut changed = false;
+
+		for details in offenders {
+			let (who, _) = &details.offender;
+
+			if validators.len() > 1 {
+				if let Some(index) = validators.iter().position(|v| v == who) {
+					validators.remove(index);
+					changed = true;
+					Self::deposit_event(RawEvent::ValidatorRemoved(who.clone()));
+				}
+			}
+		}
+
+		if changed {
+			<Validators<T>>::put(validators);
+			ValidatorsChanged::put(true);
+		}
+
+		Ok(T::DbWeight::get().reads_writes(1, 2))
+	}
+
+	fn can_report() -> bool {
+		true
+	}
+}
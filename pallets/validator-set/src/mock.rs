@@ -0,0 +1,242 @@
+use crate::{AuraEquivocationOffence, FullIdentificationOf, Module, Trait, GenesisConfig};
+use std::cell::RefCell;
+use sp_core::{H256, Pair, crypto::KeyTypeId, ed25519, sr25519};
+use frame_support::{
+	impl_outer_event, impl_outer_origin, parameter_types,
+	traits::{Get, KeyOwnerProofSystem, OnInitialize},
+	weights::Weight,
+};
+use sp_runtime::{
+	impl_opaque_keys, Perbill,
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup, OpaqueKeys}, testing::Header,
+};
+use sp_staking::offence::{Offence, OffenceError, ReportOffence};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+use frame_system as system;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod validator_set {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_session,
+		pallet_grandpa,
+		validator_set<T>,
+	}
+}
+
+impl_opaque_keys! {
+	pub struct MockSessionKeys {
+		pub aura: Aura,
+		pub grandpa: Grandpa,
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const MaxValidators: u32 = 4;
+	pub const MinimumPeriod: u64 = 1;
+	pub const Period: u64 = 5;
+	pub const Offset: u64 = 0;
+	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(33);
+}
+
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl pallet_timestamp::Trait for Test {
+	type Moment = u64;
+	type OnTimestampSet = Aura;
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+impl pallet_aura::Trait for Test {
+	type AuthorityId = AuraId;
+}
+
+impl pallet_grandpa::Trait for Test {
+	type Event = TestEvent;
+	type Call = pallet_grandpa::Call<Test>;
+	type KeyOwnerProofSystem = ();
+	type KeyOwnerProof =
+		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		GrandpaId,
+	)>>::IdentificationTuple;
+	type HandleEquivocation = ();
+	type WeightInfo = ();
+}
+
+impl pallet_session::Trait for Test {
+	type Event = TestEvent;
+	type ValidatorId = u64;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Test, ValidatorSet>;
+	type SessionHandler = <MockSessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = MockSessionKeys;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type WeightInfo = ();
+}
+
+impl pallet_session::historical::Trait for Test {
+	type FullIdentification = ();
+	type FullIdentificationOf = FullIdentificationOf;
+}
+
+pub type AuraOffence = AuraEquivocationOffence<(u64, ())>;
+
+thread_local! {
+	static REMOVE_OFFENDERS: RefCell<bool> = RefCell::new(false);
+	static OFFENCES: RefCell<Vec<(Vec<u64>, AuraOffence)>> = RefCell::new(vec![]);
+}
+
+pub struct RemoveOffenders;
+impl Get<bool> for RemoveOffenders {
+	fn get() -> bool {
+		REMOVE_OFFENDERS.with(|v| *v.borrow())
+	}
+}
+
+// Set whether offenders are removed from the set in the current test.
+pub fn set_remove_offenders(remove: bool) {
+	REMOVE_OFFENDERS.with(|v| *v.borrow_mut() = remove);
+}
+
+// Records the offences reported in the current test, rejecting duplicates as `pallet_offences`
+// does.
+pub struct OffenceRecorder;
+impl ReportOffence<u64, (u64, ()), AuraOffence> for OffenceRecorder {
+	fn report_offence(reporters: Vec<u64>, offence: AuraOffence) -> Result<(), OffenceError> {
+		if Self::is_known_offence(&offence.offenders(), &offence.time_slot()) {
+			return Err(OffenceError::DuplicateReport);
+		}
+		OFFENCES.with(|o| o.borrow_mut().push((reporters, offence)));
+		Ok(())
+	}
+
+	fn is_known_offence(offenders: &[(u64, ())], time_slot: &u64) -> bool {
+		OFFENCES.with(|o| o.borrow().iter().any(|(_, offence)| {
+			offence.time_slot() == *time_slot && offence.offenders() == offenders
+		}))
+	}
+}
+
+// The offences reported in the current test, with their reporters.
+pub fn offences() -> Vec<(Vec<u64>, AuraOffence)> {
+	OFFENCES.with(|o| o.borrow().clone())
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type AddRemoveOrigin = system::EnsureRoot<u64>;
+	type MaxValidators = MaxValidators;
+	type RemoveOffenders = RemoveOffenders;
+	type KeyOwnerProofSystem = Historical;
+	type KeyOwnerProof =
+		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, AuraId)>>::Proof;
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		AuraId,
+	)>>::IdentificationTuple;
+	type ReportOffence = OffenceRecorder;
+	type WeightInfo = ();
+}
+
+pub type System = system::Module<Test>;
+pub type Session = pallet_session::Module<Test>;
+pub type Aura = pallet_aura::Module<Test>;
+pub type Grandpa = pallet_grandpa::Module<Test>;
+pub type Historical = pallet_session::historical::Module<Test>;
+pub type ValidatorSet = Module<Test>;
+
+// The key pair validator `id` seals blocks with.
+pub fn aura_pair(id: u64) -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[id as u8; 32])
+}
+
+// The session keys of validator `id`.
+pub fn session_keys(id: u64) -> MockSessionKeys {
+	MockSessionKeys {
+		aura: AuraId::from(aura_pair(id).public()),
+		grandpa: GrandpaId::from(ed25519::Public::from_raw([id as u8; 32])),
+	}
+}
+
+// Build genesis storage with accounts 1, 2 and 3 as the validators.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let validators = vec![1, 2, 3];
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	// The validator set must be in storage before the session pallet plans the first sessions.
+	GenesisConfig::<Test> {
+		validators: validators.clone(),
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_session::GenesisConfig::<Test> {
+		keys: validators.iter().map(|&id| (id, id, session_keys(id))).collect(),
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	// Events are not deposited in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+// Run `on_initialize` for each block up to `n`, rotating sessions every `Period` blocks.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Session::on_initialize(System::block_number());
+	}
+}
+
+// The last event deposited by the validator set pallet.
+pub fn last_event() -> crate::RawEvent<u64> {
+	System::events().into_iter()
+		.filter_map(|record| match record.event {
+			TestEvent::validator_set(event) => Some(event),
+			_ => None,
+		})
+		.last()
+		.expect("the validator set pallet deposited an event")
+}
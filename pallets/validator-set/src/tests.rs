@@ -0,0 +1,310 @@
+use crate::{AuraEquivocationOffence, AuraEquivocationProof, Error, RawEvent, mock::*};
+use codec::Encode;
+use frame_support::{assert_ok, assert_noop, traits::KeyOwnerProofSystem};
+use pallet_session::{SessionManager, historical};
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_core::{H256, Pair, crypto::key_types};
+use sp_runtime::{
+	DigestItem, Perbill,
+	testing::Header,
+	traits::{BadOrigin, Header as HeaderT},
+};
+use sp_session::MembershipProof;
+use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
+
+// Report `who` for an offence in the current session.
+fn report(who: u64) {
+	report_in_session(who, Session::current_index());
+}
+
+// Report `who` for an offence in session `session`.
+fn report_in_session(who: u64, session: u32) {
+	assert_ok!(ValidatorSet::on_offence(
+		&[OffenceDetails { offender: (who, ()), reporters: vec![] }],
+		&[Perbill::zero()],
+		session,
+	));
+}
+
+// A header for slot `slot`, sealed by validator `id`. Headers with different `state_root` bytes
+// are different blocks.
+fn sealed_header(slot: u64, state_root: u8, id: u64) -> Header {
+	let mut header = Header::new(
+		1,
+		H256::default(),
+		H256::repeat_byte(state_root),
+		H256::default(),
+		Default::default(),
+	);
+	header.digest_mut().push(DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode()));
+	let signature = aura_pair(id).sign(header.hash().as_ref());
+	header.digest_mut().push(DigestItem::Seal(AURA_ENGINE_ID, signature.encode()));
+	header
+}
+
+// A proof that validator `id` owns its Aura key in the current session.
+fn key_owner_proof(id: u64) -> MembershipProof {
+	Historical::prove((key_types::AURA, session_keys(id).aura)).expect("`id` is a validator")
+}
+
+// Report that validator `offender` sealed `first_header` and `second_header` for slot `slot`,
+// from account 9.
+fn report_aura_equivocation(
+	offender: u64,
+	slot: u64,
+	first_header: Header,
+	second_header: Header,
+	key_owner_proof: MembershipProof,
+) -> sp_runtime::DispatchResult {
+	let equivocation_proof = AuraEquivocationProof {
+		offender: session_keys(offender).aura,
+		slot,
+		first_header,
+		second_header,
+	};
+	ValidatorSet::report_aura_equivocation(
+		Origin::signed(9),
+		Box::new(equivocation_proof),
+		key_owner_proof,
+	)
+}
+
+fn validator_set_events() -> Vec<RawEvent<u64>> {
+	System::events().into_iter()
+		.filter_map(|record| match record.event {
+			TestEvent::validator_set(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn genesis_validators_plan_the_first_sessions() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		// The session pallet planned them at genesis; nothing changed since.
+		assert_eq!(ValidatorSet::new_session(2), None);
+	});
+}
+
+#[test]
+fn add_validator_plans_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_eq!(last_event(), RawEvent::ValidatorAdded(4));
+		assert_eq!(ValidatorSet::new_session(2), Some(vec![1, 2, 3, 4]));
+		assert_eq!(ValidatorSet::new_session(3), None);
+	});
+}
+
+#[test]
+fn historical_sessions_are_planned_with_unit_identification() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_eq!(
+			<ValidatorSet as historical::SessionManager<_, _>>::new_session(2),
+			Some(vec![(1, ()), (2, ()), (3, ()), (4, ())]),
+		);
+		assert_eq!(<ValidatorSet as historical::SessionManager<_, _>>::new_session(3), None);
+	});
+}
+
+#[test]
+fn added_validator_takes_part_two_rotations_later() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_ok!(Session::set_keys(Origin::signed(4), session_keys(4), vec![]));
+
+		run_to_block(5);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		run_to_block(10);
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+	});
+}
+
+#[test]
+fn remove_validator_plans_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 1));
+		assert_eq!(last_event(), RawEvent::ValidatorRemoved(1));
+		assert_eq!(ValidatorSet::new_session(2), Some(vec![2, 3]));
+	});
+}
+
+#[test]
+fn only_add_remove_origin_changes_the_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), BadOrigin);
+		assert_noop!(ValidatorSet::remove_validator(Origin::signed(1), 2), BadOrigin);
+	});
+}
+
+#[test]
+fn add_validator_rejects_members_and_full_sets() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 1),
+			Error::<Test>::AlreadyValidator,
+		);
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		// `MaxValidators` is 4.
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 5),
+			Error::<Test>::TooManyValidators,
+		);
+	});
+}
+
+#[test]
+fn remove_validator_rejects_non_members_and_the_last_validator() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 4),
+			Error::<Test>::NotValidator,
+		);
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 1));
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 3),
+			Error::<Test>::TooFewValidators,
+		);
+	});
+}
+
+#[test]
+fn offenders_stay_in_the_set_unless_configured() {
+	new_test_ext().execute_with(|| {
+		report(2);
+		assert!(validator_set_events().is_empty());
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+		// Nor are they disabled, which Aura and GRANDPA would ignore.
+		assert!(Session::disabled_validators().is_empty());
+	});
+}
+
+#[test]
+fn offenders_are_removed_at_the_next_rotation_if_configured() {
+	new_test_ext().execute_with(|| {
+		set_remove_offenders(true);
+		report(2);
+		assert_eq!(validator_set_events(), vec![RawEvent::ValidatorRemoved(2)]);
+		assert_eq!(ValidatorSet::validators(), vec![1, 3]);
+		assert!(Session::disabled_validators().is_empty());
+
+		run_to_block(10);
+		assert_eq!(Session::validators(), vec![1, 3]);
+	});
+}
+
+#[test]
+fn offences_from_earlier_sessions_also_remove_offenders() {
+	new_test_ext().execute_with(|| {
+		run_to_block(5);
+		System::reset_events();
+
+		set_remove_offenders(true);
+		report_in_session(2, 0);
+		assert_eq!(validator_set_events(), vec![RawEvent::ValidatorRemoved(2)]);
+		assert_eq!(ValidatorSet::validators(), vec![1, 3]);
+	});
+}
+
+#[test]
+fn the_last_validator_is_not_removed() {
+	new_test_ext().execute_with(|| {
+		set_remove_offenders(true);
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 1));
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+		System::reset_events();
+
+		report(3);
+		assert!(validator_set_events().is_empty());
+		assert_eq!(ValidatorSet::validators(), vec![3]);
+	});
+}
+
+#[test]
+fn offences_by_non_validators_are_ignored() {
+	new_test_ext().execute_with(|| {
+		set_remove_offenders(true);
+		report(9);
+		assert!(validator_set_events().is_empty());
+		assert_eq!(ValidatorSet::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn aura_equivocations_are_reported_as_offences() {
+	new_test_ext().execute_with(|| {
+		let first = sealed_header(7, 1, 2);
+		let second = sealed_header(7, 2, 2);
+		assert_ok!(report_aura_equivocation(
+			2,
+			7,
+			first.clone(),
+			second.clone(),
+			key_owner_proof(2),
+		));
+		assert_eq!(offences(), vec![(vec![9], AuraEquivocationOffence {
+			slot: 7,
+			session_index: 0,
+			validator_set_count: 3,
+			offender: (2, ()),
+		})]);
+
+		// The same equivocation cannot be reported twice, even with the headers swapped.
+		assert_noop!(
+			report_aura_equivocation(2, 7, second, first, key_owner_proof(2)),
+			Error::<Test>::DuplicateOffenceReport,
+		);
+	});
+}
+
+#[test]
+fn aura_equivocations_need_two_blocks_sealed_by_the_offender_in_the_slot() {
+	new_test_ext().execute_with(|| {
+		let header = sealed_header(7, 1, 2);
+		let invalid = |first: Header, second: Header| {
+			assert_noop!(
+				report_aura_equivocation(2, 7, first, second, key_owner_proof(2)),
+				Error::<Test>::InvalidEquivocationProof,
+			);
+		};
+
+		// The same block twice.
+		invalid(header.clone(), header.clone());
+		// Blocks of different slots.
+		invalid(header.clone(), sealed_header(8, 2, 2));
+		// A block sealed by another validator.
+		invalid(header.clone(), sealed_header(7, 2, 3));
+		// A block without a seal.
+		let mut unsealed = sealed_header(7, 2, 2);
+		unsealed.digest_mut().pop();
+		invalid(header.clone(), unsealed);
+		// A block whose seal does not match it.
+		let mut tampered = sealed_header(7, 2, 2);
+		tampered.number = 2;
+		invalid(header, tampered);
+
+		assert!(offences().is_empty());
+	});
+}
+
+#[test]
+fn aura_equivocations_need_the_offender_to_be_a_validator() {
+	new_test_ext().execute_with(|| {
+		// Account 9 has no session keys, so no proof ties its key to a validator.
+		assert_noop!(
+			report_aura_equivocation(
+				9,
+				7,
+				sealed_header(7, 1, 9),
+				sealed_header(7, 2, 9),
+				key_owner_proof(2),
+			),
+			Error::<Test>::InvalidKeyOwnershipProof,
+		);
+		assert!(offences().is_empty());
+	});
+}
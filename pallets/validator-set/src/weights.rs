@@ -0,0 +1,62 @@
+//! Weights for pallet_validator_set
+
+// PLACEHOLDER: not benchmarked. These values are estimates, not measurements. Regenerate this
+// file with `scripts/benchmark.sh` on reference hardware before relying on them; it runs:
+
+// ./target/release/node-template
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_validator_set
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./pallets/validator-set/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{Weight, constants::RocksDbWeight};
+use frame_support::traits::Get;
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_validator_set.
+pub trait WeightInfo {
+	fn add_validator(v: u32, ) -> Weight;
+	fn remove_validator(v: u32, ) -> Weight;
+}
+
+/// Placeholder weights for pallet_validator_set, to be replaced by benchmarked ones.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn add_validator(v: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((110_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_validator(v: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn add_validator(v: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((110_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_validator(v: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
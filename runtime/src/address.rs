@@ -0,0 +1,61 @@
+//! The address format of the runtime, which names an account by its id, its index or raw bytes.
+
+use crate::{AccountId, AccountIndex, Indices};
+use codec::{Decode, Encode};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{LookupError, StaticLookup},
+};
+use sp_std::{convert::TryFrom, prelude::*};
+
+/// A multi-format address: an account id, a compact account index or the account id as raw bytes.
+///
+/// Runtimes before `spec_version` 9, with `transaction_version` 3 or less, used a bare account id.
+/// Those encodings are not read back as the same address: depending on the account id's first byte
+/// they fail to decode or decode as another variant. Clients must choose the address type by
+/// `spec_version`; the README gives polkadot-js custom types for both sides of the change. The
+/// variant order is part of the encoding, so new variants may only be appended.
+#[derive(Encode, Decode, PartialEq, Eq, Clone, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Hash))]
+pub enum MultiAddress<AccountId, AccountIndex> {
+	/// An account id.
+	Id(AccountId),
+	/// An account index, allocated by `pallet_indices`.
+	Index(#[codec(compact)] AccountIndex),
+	/// An account id given as raw bytes.
+	Raw(Vec<u8>),
+}
+
+impl<AccountId, AccountIndex> From<AccountId> for MultiAddress<AccountId, AccountIndex> {
+	fn from(id: AccountId) -> Self {
+		MultiAddress::Id(id)
+	}
+}
+
+impl<AccountId: Default, AccountIndex> Default for MultiAddress<AccountId, AccountIndex> {
+	fn default() -> Self {
+		MultiAddress::Id(Default::default())
+	}
+}
+
+/// Looks up the account a `MultiAddress` names, resolving indices through `Indices`.
+pub struct MultiAddressLookup;
+
+impl StaticLookup for MultiAddressLookup {
+	type Source = MultiAddress<AccountId, AccountIndex>;
+	type Target = AccountId;
+
+	fn lookup(address: Self::Source) -> Result<AccountId, LookupError> {
+		match address {
+			MultiAddress::Id(id) => Ok(id),
+			MultiAddress::Index(index) => Indices::lookup_index(index).ok_or(LookupError),
+			MultiAddress::Raw(bytes) => <[u8; 32]>::try_from(&bytes[..])
+				.map(AccountId::from)
+				.map_err(|_| LookupError),
+		}
+	}
+
+	fn unlookup(id: AccountId) -> Self::Source {
+		MultiAddress::Id(id)
+	}
+}
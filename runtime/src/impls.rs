@@ -0,0 +1,93 @@
+//! Implementations of runtime traits that are not provided by a pallet.
+
+use crate::{
+	AccountId, Balance, Balances, Council, CouncilMaxMembers, Runtime, System, Treasury,
+	CENTS,
+};
+use frame_support::{
+	traits::{
+		Contains, ContainsLengthBound, Currency, FindAuthor, Get, Imbalance, OnUnbalanced,
+	},
+	weights::{
+		constants::ExtrinsicBaseWeight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+		WeightToFeePolynomial,
+	},
+};
+use smallvec::smallvec;
+use sp_runtime::Perbill;
+use sp_std::prelude::*;
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Converts weight to fee so that `ExtrinsicBaseWeight`, the weight of the cheapest extrinsic,
+/// costs a tenth of a cent before the fee multiplier is applied.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let p = CENTS / 10;
+		let q = Balance::from(ExtrinsicBaseWeight::get());
+		smallvec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational_approximation(p % q, q),
+			coeff_integer: p / q,
+		}]
+	}
+}
+
+/// Credits an imbalance to the author of the current block, or to the treasury if the block has
+/// no author.
+pub struct Author;
+
+impl OnUnbalanced<NegativeImbalance> for Author {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		// `Authorship::author` would fall back to the default account for blocks sealed without an
+		// Aura pre-runtime digest, such as manually sealed ones.
+		let digest = System::digest();
+		let pre_runtime_digests = digest.logs().iter().filter_map(|d| d.as_pre_runtime());
+		match <Runtime as pallet_authorship::Trait>::FindAuthor::find_author(pre_runtime_digests) {
+			Some(author) => Balances::resolve_creating(&author, amount),
+			None => <Treasury as OnUnbalanced<_>>::on_unbalanced(amount),
+		}
+	}
+}
+
+/// Splits transaction fees 80% to the treasury and 20% to the block author. Tips go to the block
+/// author in full.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let (to_treasury, mut to_author) = fees.ration(80, 20);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.merge_into(&mut to_author);
+			}
+			<Treasury as OnUnbalanced<_>>::on_unbalanced(to_treasury);
+			<Author as OnUnbalanced<_>>::on_unbalanced(to_author);
+		}
+	}
+}
+
+/// Makes the council members the accounts that may tip.
+pub struct CouncilTippers;
+
+impl Contains<AccountId> for CouncilTippers {
+	fn sorted_members() -> Vec<AccountId> {
+		// `pallet_collective` keeps its members sorted.
+		Council::members()
+	}
+}
+
+impl ContainsLengthBound for CouncilTippers {
+	fn min_len() -> usize {
+		0
+	}
+
+	fn max_len() -> usize {
+		CouncilMaxMembers::get() as usize
+	}
+}
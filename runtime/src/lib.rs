@@ -7,18 +7,26 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_std::prelude::*;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::KeyTypeId, OpaqueMetadata,
+	u32_trait::{_1, _2, _3, _4},
+};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
+	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, FixedPointNumber,
+	ModuleId, MultiSignature, Percent, RuntimeDebug,
 	transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	BlakeTwo256, Block as BlockT, ConvertInto, Verify, IdentifyAccount, NumberFor,
+	OpaqueKeys, Saturating,
 };
 use sp_api::impl_runtime_apis;
+use codec::{Decode, Encode};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
 use pallet_grandpa::fg_primitives;
+use pallet_session::historical as pallet_session_historical;
+use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 use sp_version::RuntimeVersion;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -28,19 +36,30 @@ use sp_version::NativeVersion;
 pub use sp_runtime::BuildStorage;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_balances::Call as BalancesCall;
-pub use sp_runtime::{Permill, Perbill};
+pub use sp_runtime::{Permill, Perbill, Perquintill};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
-	traits::{KeyOwnerProofSystem, Randomness},
+	traits::{InstanceFilter, KeyOwnerProofSystem, Randomness},
 	weights::{
 		Weight, IdentityFee,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 	},
 };
 
+pub mod address;
+pub mod impls;
+pub mod migrations;
+
 /// Import the template pallet.
 pub use pallet_template;
 
+pub use pallet_validator_set;
+
+pub use pallet_assets;
+
+#[cfg(test)]
+mod tests;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -58,6 +77,9 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of an asset issued through the assets pallet.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -95,10 +117,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
 	impl_name: create_runtime_str!("node-template"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 13,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 5,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -110,6 +132,18 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+/// Symbol of the native token, as shown by wallets.
+pub const TOKEN_SYMBOL: &str = "UNIT";
+/// Number of decimals of the native token, as shown by wallets.
+pub const TOKEN_DECIMALS: u32 = 12;
+/// SS58 prefix of the chain's addresses. 42 is the generic Substrate address format.
+pub const SS58_PREFIX: u8 = 42;
+
+// Amounts of the native token, which has `TOKEN_DECIMALS` decimals.
+pub const UNITS: Balance = 1_000_000_000_000;
+pub const CENTS: Balance = UNITS / 100;
+pub const MILLICENTS: Balance = CENTS / 1_000;
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -129,6 +163,7 @@ parameter_types! {
 		.saturating_sub(Perbill::from_percent(10)) * MaximumBlockWeight::get();
 	pub const MaximumBlockLength: u32 = 5 * 1024 * 1024;
 	pub const Version: RuntimeVersion = VERSION;
+	pub const SS58Prefix: u8 = SS58_PREFIX;
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -141,7 +176,7 @@ impl frame_system::Trait for Runtime {
 	/// The aggregated dispatch type that is available for extrinsics.
 	type Call = Call;
 	/// The lookup mechanism to get account ID from whatever is passed in dispatchers.
-	type Lookup = IdentityLookup<AccountId>;
+	type Lookup = address::MultiAddressLookup;
 	/// The index type for storing how many extrinsics an account has signed.
 	type Index = Index;
 	/// The index type for blocks.
@@ -192,6 +227,71 @@ impl frame_system::Trait for Runtime {
 	type SystemWeightInfo = ();
 }
 
+parameter_types! {
+	/// Validator set changes are planned at the end of one session and take effect at the end of
+	/// the next.
+	pub const SessionPeriod: BlockNumber = 10 * MINUTES;
+	pub const SessionOffset: BlockNumber = 0;
+	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(17);
+}
+
+impl pallet_session::Trait for Runtime {
+	type Event = Event;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, ValidatorSet>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type WeightInfo = ();
+}
+
+impl pallet_session::historical::Trait for Runtime {
+	type FullIdentification = ();
+	type FullIdentificationOf = pallet_validator_set::FullIdentificationOf;
+}
+
+parameter_types! {
+	pub const MaxValidators: u32 = 100;
+	/// Equivocating authorities are removed from the set and leave after the next session.
+	pub const RemoveOffenders: bool = true;
+}
+
+impl pallet_validator_set::Trait for Runtime {
+	type Event = Event;
+	type AddRemoveOrigin = EnsureRootOrTwoThirdsCouncil;
+	type MaxValidators = MaxValidators;
+	type RemoveOffenders = RemoveOffenders;
+
+	type KeyOwnerProofSystem = Historical;
+
+	type KeyOwnerProof =
+		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, AuraId)>>::Proof;
+
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		AuraId,
+	)>>::IdentificationTuple;
+
+	type ReportOffence = Offences;
+
+	type WeightInfo = pallet_validator_set::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const IndexDeposit: Balance = UNITS;
+}
+
+impl pallet_indices::Trait for Runtime {
+	type AccountIndex = AccountIndex;
+	type Currency = Balances;
+	type Deposit = IndexDeposit;
+	type Event = Event;
+	type WeightInfo = ();
+}
+
 impl pallet_aura::Trait for Runtime {
 	type AuthorityId = AuraId;
 }
@@ -200,7 +300,7 @@ impl pallet_grandpa::Trait for Runtime {
 	type Event = Event;
 	type Call = Call;
 
-	type KeyOwnerProofSystem = ();
+	type KeyOwnerProofSystem = Historical;
 
 	type KeyOwnerProof =
 		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
@@ -210,11 +310,23 @@ impl pallet_grandpa::Trait for Runtime {
 		GrandpaId,
 	)>>::IdentificationTuple;
 
-	type HandleEquivocation = ();
+	type HandleEquivocation =
+		pallet_grandpa::EquivocationHandler<Self::KeyOwnerIdentification, Offences>;
 
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub OffencesWeightSoftLimit: Weight = Perbill::from_percent(60) * MaximumBlockWeight::get();
+}
+
+impl pallet_offences::Trait for Runtime {
+	type Event = Event;
+	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ValidatorSet;
+	type WeightSoftLimit = OffencesWeightSoftLimit;
+}
+
 parameter_types! {
 	pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
 }
@@ -238,32 +350,405 @@ impl pallet_balances::Trait for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
+	type DustRemoval = Treasury;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 1;
+	pub const TransactionByteFee: Balance = 10 * MILLICENTS;
+	/// The share of the normal block weight that the fee multiplier aims to keep blocks at.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How fast the fee multiplier follows block fullness away from the target.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
+	/// The fee multiplier never falls below this, so that fees never reach zero.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
 }
 
 impl pallet_transaction_payment::Trait for Runtime {
 	type Currency = Balances;
-	type OnTransactionPayment = ();
+	type OnTransactionPayment = impls::DealWithFees;
 	type TransactionByteFee = TransactionByteFee;
-	type WeightToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type WeightToFee = impls::WeightToFee;
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+}
+
+parameter_types! {
+	/// Uncles are not used with Aura.
+	pub const UncleGenerations: BlockNumber = 0;
 }
 
-impl pallet_sudo::Trait for Runtime {
+impl pallet_authorship::Trait for Runtime {
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	type UncleGenerations = UncleGenerations;
+	type FilterUncle = ();
+	type EventHandler = ();
+}
+
+parameter_types! {
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = UNITS;
+	pub const SpendPeriod: BlockNumber = DAYS;
+	/// Unspent funds stay in the treasury.
+	pub const Burn: Permill = Permill::from_percent(0);
+	pub const TipCountdown: BlockNumber = DAYS;
+	pub const TipFindersFee: Percent = Percent::from_percent(20);
+	pub const TipReportDepositBase: Balance = UNITS;
+	pub const DataDepositPerByte: Balance = CENTS;
+	pub const BountyDepositBase: Balance = UNITS;
+	pub const BountyDepositPayoutDelay: BlockNumber = DAYS;
+	pub const BountyUpdatePeriod: BlockNumber = 14 * DAYS;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: Balance = 5 * UNITS;
+	pub const MaximumReasonLength: u32 = 16384;
+}
+
+impl pallet_treasury::Trait for Runtime {
+	type ModuleId = TreasuryModuleId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRootOrTwoThirdsCouncil;
+	type RejectOrigin = EnsureRootOrHalfCouncil;
+	type Tippers = impls::CouncilTippers;
+	type TipCountdown = TipCountdown;
+	type TipFindersFee = TipFindersFee;
+	type TipReportDepositBase = TipReportDepositBase;
+	type DataDepositPerByte = DataDepositPerByte;
+	type Event = Event;
+	type OnSlash = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BountyDepositBase = BountyDepositBase;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type MaximumReasonLength = MaximumReasonLength;
+	type BurnDestination = ();
+	type WeightInfo = ();
+}
+
+/// Root, dispatched by referenda, or more than half of the council.
+pub type EnsureRootOrHalfCouncil = frame_system::EnsureOneOf<
+	AccountId,
+	frame_system::EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+>;
+
+/// Root, dispatched by referenda, or at least two thirds of the council.
+pub type EnsureRootOrTwoThirdsCouncil = frame_system::EnsureOneOf<
+	AccountId,
+	frame_system::EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>,
+>;
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+pub type CouncilCollective = pallet_collective::Instance1;
+
+impl pallet_collective::Trait<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = ();
+}
+
+/// Council seats are changed by root or two thirds of the council.
+impl pallet_membership::Trait<pallet_membership::Instance1> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrTwoThirdsCouncil;
+	type RemoveOrigin = EnsureRootOrTwoThirdsCouncil;
+	type SwapOrigin = EnsureRootOrTwoThirdsCouncil;
+	type ResetOrigin = EnsureRootOrTwoThirdsCouncil;
+	type PrimeOrigin = EnsureRootOrTwoThirdsCouncil;
+	type MembershipInitialized = Council;
+	type MembershipChanged = Council;
+}
+
+parameter_types! {
+	pub const TechnicalMotionDuration: BlockNumber = 3 * DAYS;
+	pub const TechnicalMaxProposals: u32 = 100;
+	pub const TechnicalMaxMembers: u32 = 100;
+}
+
+pub type TechnicalCollective = pallet_collective::Instance2;
+
+impl pallet_collective::Trait<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = TechnicalMotionDuration;
+	type MaxProposals = TechnicalMaxProposals;
+	type MaxMembers = TechnicalMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = ();
+}
+
+/// Technical committee seats are changed by root or more than half of the council.
+impl pallet_membership::Trait<pallet_membership::Instance2> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrHalfCouncil;
+	type RemoveOrigin = EnsureRootOrHalfCouncil;
+	type SwapOrigin = EnsureRootOrHalfCouncil;
+	type ResetOrigin = EnsureRootOrHalfCouncil;
+	type PrimeOrigin = EnsureRootOrHalfCouncil;
+	type MembershipInitialized = TechnicalCommittee;
+	type MembershipChanged = TechnicalCommittee;
+}
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const InstantAllowed: bool = true;
+	pub const MinimumDeposit: Balance = 100 * UNITS;
+	pub const EnactmentPeriod: BlockNumber = 8 * DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const PreimageByteDeposit: Balance = CENTS;
+	pub const MaxVotes: u32 = 100;
+}
+
+/// Referenda are proposed by the public or the council, and the technical committee may fast-track
+/// those from the council. Passed referenda are dispatched as root, which is how `set_code` and
+/// other root calls are made.
+impl pallet_democracy::Trait for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// More than half of the council may table a referendum that passes by super-majority.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>;
+	/// Three quarters of the council may table a referendum that passes by simple majority.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>;
+	/// The whole council may table a referendum that fails only by super-majority.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+	/// The whole technical committee may fast-track below `FastTrackVotingPeriod`.
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, TechnicalCollective>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	/// Any technical committee member may veto a council proposal once, for `CooloffPeriod`.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	/// Scheduled calls leave a fifth of the block to transactions. The first call due in a block,
+	/// and calls with a hard-deadline priority, run even if they exceed this.
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+/// Dispatches calls at a given block, optionally by name and periodically. Root schedules calls,
+/// such as `set_code` from a referendum, and referenda are enacted through it.
+impl pallet_scheduler::Trait for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: Balance = UNITS;
+}
+
+impl pallet_vesting::Trait for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = ();
+}
+
+impl pallet_utility::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	/// Reserved by the first approval of a multisig operation, for its entry in storage.
+	pub const MultisigDepositBase: Balance = UNITS;
+	/// Reserved for each approval a multisig operation needs.
+	pub const MultisigDepositFactor: Balance = 10 * CENTS;
+	pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = MultisigDepositBase;
+	type DepositFactor = MultisigDepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	/// Reserved by an account that has proxies, for its entry in storage.
+	pub const ProxyDepositBase: Balance = UNITS;
+	/// Reserved for each proxy.
+	pub const ProxyDepositFactor: Balance = 10 * CENTS;
+	pub const MaxProxies: u16 = 32;
+	pub const MaxPending: u32 = 32;
+	pub const AnnouncementDepositBase: Balance = UNITS;
+	pub const AnnouncementDepositFactor: Balance = 20 * CENTS;
+}
+
+/// The calls a proxy may make for the account it acts for.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Calls known not to move balances. Calls that dispatch other calls are only allowed where
+	/// the dispatched calls are filtered too.
+	NonTransfer,
+	/// Calls to the council, the technical committee, referenda and the treasury.
+	Governance,
+	/// Calls to the template pallet.
+	TemplateOnly,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			// An allowlist, so that pallets added later are not proxied until they are reviewed.
+			ProxyType::NonTransfer => matches!(c,
+				Call::System(..) | Call::Timestamp(..) | Call::ValidatorSet(..) | Call::Session(..)
+					| Call::Grandpa(..) | Call::Authorship(..) | Call::TemplateModule(..)
+					| Call::Treasury(..) | Call::Council(..) | Call::CouncilMembership(..)
+					| Call::TechnicalCommittee(..) | Call::TechnicalMembership(..)
+					| Call::Democracy(..)
+					| Call::Indices(pallet_indices::Call::claim(..))
+					| Call::Indices(pallet_indices::Call::free(..))
+					| Call::Indices(pallet_indices::Call::freeze(..))
+					| Call::Utility(..) | Call::Proxy(..)
+					| Call::Vesting(pallet_vesting::Call::vest(..))
+					| Call::Vesting(pallet_vesting::Call::vest_other(..))
+			),
+			ProxyType::Governance => matches!(c,
+				Call::Council(..) | Call::TechnicalCommittee(..) | Call::Democracy(..)
+					| Call::Treasury(..) | Call::Utility(..)
+			),
+			ProxyType::TemplateOnly => matches!(c, Call::TemplateModule(..) | Call::Utility(..)),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, _) => true,
+			_ => false,
+		}
+	}
+}
+
+impl pallet_proxy::Trait for Runtime {
 	type Event = Event;
 	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = ();
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	/// Reserved by the creator of an asset until the asset is destroyed.
+	pub const AssetDeposit: Balance = 100 * UNITS;
+	/// Reserved by the owner of an asset for its metadata.
+	pub const MetadataDepositBase: Balance = 10 * UNITS;
+	/// Reserved for each byte of an asset's name and symbol.
+	pub const MetadataDepositPerByte: Balance = CENTS;
+	pub const StringLimit: u32 = 50;
+	/// Holdings are not paid for by their holders, so `AssetDeposit` covers this many.
+	pub const MaxHolders: u32 = 1_000;
+}
+
+/// Configure the assets pallet in pallets/assets.
+impl pallet_assets::Trait for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type StringLimit = StringLimit;
+	type MaxHolders = MaxHolders;
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MinValue: u64 = 0;
+	pub const MaxValue: u64 = 1_000_000;
+	pub const MaxHistory: u32 = 100;
+	pub const StorageDeposit: Balance = 10 * ExistentialDeposit::get();
 }
 
 /// Configure the template pallet in pallets/template.
 impl pallet_template::Trait for Runtime {
 	type Event = Event;
+	type MinValue = MinValue;
+	type MaxValue = MaxValue;
+	type OverflowPolicy = pallet_template::FailOnOverflow;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxHistory = MaxHistory;
+	type Currency = Balances;
+	type StorageDeposit = StorageDeposit;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+}
+
+/// Configure the chain info pallet in pallets/chain-info.
+impl pallet_chain_info::Trait for Runtime {
+	type SS58Prefix = SS58Prefix;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -276,18 +761,42 @@ construct_runtime!(
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
 		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Module, Call, Storage},
 		Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
+		// The validator set must come before the session pallet, which plans the first sessions
+		// from it at genesis.
+		ValidatorSet: pallet_validator_set::{Module, Call, Storage, Config<T>, Event<T>},
+		Session: pallet_session::{Module, Call, Storage, Event, Config<T>},
 		Aura: pallet_aura::{Module, Config<T>, Inherent},
-		Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event},
+		Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event, ValidateUnsigned},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
-		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
-		TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
+		TemplateModule: pallet_template::{Module, Call, Config<T>, Storage, Event<T>},
+		Historical: pallet_session_historical::{Module},
+		Offences: pallet_offences::{Module, Call, Storage, Event},
+		Authorship: pallet_authorship::{Module, Call, Storage, Inherent},
+		Treasury: pallet_treasury::{Module, Call, Storage, Config, Event<T>},
+		Council:
+			pallet_collective::<Instance1>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
+		CouncilMembership:
+			pallet_membership::<Instance1>::{Module, Call, Storage, Event<T>, Config<T>},
+		TechnicalCommittee:
+			pallet_collective::<Instance2>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
+		TechnicalMembership:
+			pallet_membership::<Instance2>::{Module, Call, Storage, Event<T>, Config<T>},
+		Democracy: pallet_democracy::{Module, Call, Storage, Config, Event<T>},
+		Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
+		Indices: pallet_indices::{Module, Call, Storage, Config<T>, Event<T>},
+		Utility: pallet_utility::{Module, Call, Event},
+		Multisig: pallet_multisig::{Module, Call, Storage, Event<T>},
+		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
+		Vesting: pallet_vesting::{Module, Call, Storage, Event<T>, Config<T>},
+		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
+		ChainInfo: pallet_chain_info::{Module},
 	}
 );
 
 /// The address format for describing accounts.
-pub type Address = AccountId;
+pub type Address = address::MultiAddress<AccountId, AccountIndex>;
 /// Block header type as expected by this runtime.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
 /// Block type as expected by this runtime.
@@ -317,8 +826,18 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllModules,
+	(migrations::RemoveSudo, migrations::TemplateMigrations),
 >;
 
+/// Lets pallets submit unsigned transactions, such as GRANDPA equivocation reports, from
+/// offchain contexts.
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -409,23 +928,39 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(
+				equivocation_proof,
+				key_owner_proof,
+			)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
+		}
+	}
+
+	impl pallet_validator_set_runtime_api::ValidatorSetApi<
+		Block,
+		AuraId,
+		sp_session::MembershipProof,
+	> for Runtime {
+		fn generate_aura_key_ownership_proof(
+			authority_id: AuraId,
+		) -> Option<sp_session::MembershipProof> {
+			Historical::prove((sp_core::crypto::key_types::AURA, authority_id))
 		}
 	}
 
@@ -445,6 +980,26 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_template_rpc_runtime_api::TemplateApi<Block, AccountId> for Runtime {
+		fn get_something() -> Option<u64> {
+			TemplateModule::something()
+		}
+
+		fn get_something_of(who: AccountId) -> Option<u64> {
+			TemplateModule::something_of(who)
+		}
+
+		fn next_something_of(who: AccountId) -> Option<u64> {
+			TemplateModule::next_something_of(&who).ok()
+		}
+	}
+
+	impl pallet_assets_rpc_runtime_api::AssetsApi<Block, AccountId, AssetId, Balance> for Runtime {
+		fn account_balances(who: AccountId) -> Vec<(AssetId, Balance)> {
+			Assets::account_balances(who)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -479,6 +1034,9 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_validator_set, ValidatorSet);
+			add_benchmark!(params, batches, pallet_assets, Assets);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
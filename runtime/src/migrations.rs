@@ -0,0 +1,42 @@
+//! Storage migrations run by `Executive` when the runtime is upgraded.
+
+use crate::{Runtime, Treasury};
+use frame_support::{storage::unhashed, traits::{Get, OnRuntimeUpgrade}, weights::Weight};
+use sp_core::hashing::twox_128;
+use sp_std::prelude::*;
+
+/// Deletes the sudo key left by runtimes that included the sudo pallet.
+///
+/// The key was the sudo pallet's only storage. Root calls are made by referenda instead. Once the
+/// key is gone the upgrade does nothing, so it runs once however many upgrades include it.
+pub struct RemoveSudo;
+
+impl RemoveSudo {
+	/// The storage key of the sudo pallet's `Key`.
+	pub fn key() -> Vec<u8> {
+		[twox_128(b"Sudo"), twox_128(b"Key")].concat()
+	}
+}
+
+impl OnRuntimeUpgrade for RemoveSudo {
+	fn on_runtime_upgrade() -> Weight {
+		let key = Self::key();
+		if !unhashed::exists(&key) {
+			return <Runtime as frame_system::Trait>::DbWeight::get().reads(1);
+		}
+		unhashed::kill(&key);
+		<Runtime as frame_system::Trait>::DbWeight::get().reads_writes(1, 1)
+	}
+}
+
+/// Runs the template pallet's pending migrations.
+///
+/// A chain that stored a global value before values were kept per account gives it to the
+/// treasury account, which no one can sign for; the force origin may set or kill it.
+pub struct TemplateMigrations;
+
+impl OnRuntimeUpgrade for TemplateMigrations {
+	fn on_runtime_upgrade() -> Weight {
+		pallet_template::migrations::migrate::<Runtime>(&Treasury::account_id())
+	}
+}
@@ -0,0 +1,82 @@
+//! Assets issued through `Assets`, with deposits paid in `Balances`.
+
+use super::*;
+use frame_support::assert_ok;
+use sp_runtime::DispatchError;
+
+const ID: AssetId = 7;
+
+// Have Alice create asset `ID` with a minimum balance of 10, name it and mint 1_000 of it to
+// herself.
+fn create_token() {
+	let alice = Sr25519Keyring::Alice.to_account_id();
+	let origin = || Origin::signed(alice.clone());
+	assert_ok!(Assets::create(origin(), ID, 10));
+	assert_ok!(Assets::set_metadata(origin(), ID, b"Token".to_vec(), b"TKN".to_vec(), 12));
+	assert_ok!(Assets::mint(origin(), ID, alice.clone().into(), 1_000));
+}
+
+#[test]
+fn asset_deposits_are_reserved_from_the_native_balance() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		initialize_block(1);
+
+		let create = Call::Assets(pallet_assets::Call::create(ID, 10));
+		let xt = sign(Sr25519Keyring::Alice, 0, create);
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		assert_eq!(Balances::reserved_balance(&alice), AssetDeposit::get());
+
+		let set_metadata = Call::Assets(pallet_assets::Call::set_metadata(
+			ID,
+			b"Token".to_vec(),
+			b"TKN".to_vec(),
+			12,
+		));
+		let xt = sign(Sr25519Keyring::Alice, 1, set_metadata);
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		let metadata_deposit = MetadataDepositBase::get() + 8 * MetadataDepositPerByte::get();
+		assert_eq!(Balances::reserved_balance(&alice), AssetDeposit::get() + metadata_deposit);
+		finalize_block();
+	});
+}
+
+#[test]
+fn account_balances_lists_every_asset_held() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		create_token();
+		assert_ok!(Assets::create(Origin::signed(bob.clone()), ID + 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(bob.clone()), ID + 1, alice.clone().into(), 5));
+
+		assert_ok!(Assets::transfer(Origin::signed(alice.clone()), ID, bob.clone().into(), 400));
+
+		let mut balances = Assets::account_balances(alice);
+		balances.sort();
+		assert_eq!(balances, vec![(ID, 600), (ID + 1, 5)]);
+		assert_eq!(Assets::account_balances(bob), vec![(ID, 400)]);
+	});
+}
+
+#[test]
+fn non_transfer_proxies_cannot_move_assets() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		initialize_block(1);
+		create_token();
+		assert_ok!(Proxy::add_proxy(
+			Origin::signed(alice.clone()),
+			bob.clone(),
+			ProxyType::NonTransfer,
+			0,
+		));
+
+		let transfer = Call::Assets(pallet_assets::Call::transfer(ID, bob.clone().into(), 100));
+		assert_ok!(Proxy::proxy(Origin::signed(bob), alice, None, Box::new(transfer)));
+		assert!(events().contains(&Event::pallet_proxy(
+			pallet_proxy::RawEvent::ProxyExecuted(Err(DispatchError::BadOrigin)),
+		)));
+	});
+}
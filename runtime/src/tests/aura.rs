@@ -0,0 +1,117 @@
+//! Aura equivocation reports, checked by `ValidatorSet`, proven against `Historical` and reported
+//! to `Offences`.
+
+use super::*;
+use pallet_validator_set_runtime_api::runtime_decl_for_ValidatorSetApi::ValidatorSetApi;
+
+type EquivocationProof = pallet_validator_set::AuraEquivocationProof<Header, AuraId>;
+
+// A block 1 header for `slot` with the given state root, sealed by `author` as the Aura client
+// seals blocks.
+fn sealed_header(slot: u64, state_root: u8, author: Sr25519Keyring) -> Header {
+	let mut header = Header::new(
+		1,
+		Default::default(),
+		Hash::repeat_byte(state_root),
+		System::block_hash(0),
+		generic::Digest {
+			logs: vec![DigestItem::PreRuntime(sp_consensus_aura::AURA_ENGINE_ID, slot.encode())],
+		},
+	);
+	let signature = author.sign(header.hash().as_ref());
+	let seal = DigestItem::Seal(sp_consensus_aura::AURA_ENGINE_ID, signature.encode());
+	header.digest_mut().push(seal);
+	header
+}
+
+fn equivocation_proof(slot: u64, offender: Sr25519Keyring) -> EquivocationProof {
+	EquivocationProof {
+		offender: offender.public().into(),
+		slot,
+		first_header: sealed_header(slot, 1, offender),
+		second_header: sealed_header(slot, 2, offender),
+	}
+}
+
+fn report(
+	reporter: Sr25519Keyring,
+	nonce: Index,
+	equivocation_proof: EquivocationProof,
+	key_owner_proof: sp_session::MembershipProof,
+) -> UncheckedExtrinsic {
+	sign(reporter, nonce, Call::ValidatorSet(
+		pallet_validator_set::Call::report_aura_equivocation(
+			Box::new(equivocation_proof),
+			key_owner_proof,
+		),
+	))
+}
+
+#[test]
+fn double_sealed_slot_is_reported_as_an_offence() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+		let equivocation_proof = equivocation_proof(2, Sr25519Keyring::Alice);
+		let key_owner_proof = Runtime::generate_aura_key_ownership_proof(
+			Sr25519Keyring::Alice.public().into(),
+		).expect("Alice is a current validator");
+
+		let xt = report(
+			Sr25519Keyring::Charlie,
+			0,
+			equivocation_proof.clone(),
+			key_owner_proof.clone(),
+		);
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		assert!(events().iter().any(|event| matches!(
+			event,
+			Event::pallet_offences(pallet_offences::Event::Offence(..)),
+		)));
+
+		// Alice is removed from the set and leaves after the next session.
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		assert_eq!(ValidatorSet::validators(), vec![Sr25519Keyring::Bob.to_account_id()]);
+		assert!(events().contains(&Event::pallet_validator_set(
+			pallet_validator_set::RawEvent::ValidatorRemoved(alice),
+		)));
+
+		// The same offence cannot be reported twice.
+		let xt = report(Sr25519Keyring::Charlie, 1, equivocation_proof, key_owner_proof);
+		assert_eq!(
+			Executive::apply_extrinsic(xt),
+			Ok(Err(pallet_validator_set::Error::<Runtime>::DuplicateOffenceReport.into())),
+		);
+		finalize_block();
+	});
+}
+
+#[test]
+fn headers_sealed_by_someone_else_are_not_an_equivocation() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+		let mut equivocation_proof = equivocation_proof(2, Sr25519Keyring::Alice);
+		equivocation_proof.second_header = sealed_header(2, 2, Sr25519Keyring::Bob);
+		let key_owner_proof = Runtime::generate_aura_key_ownership_proof(
+			Sr25519Keyring::Alice.public().into(),
+		).expect("Alice is a current validator");
+
+		let xt = report(Sr25519Keyring::Charlie, 0, equivocation_proof, key_owner_proof);
+		assert_eq!(
+			Executive::apply_extrinsic(xt),
+			Ok(Err(pallet_validator_set::Error::<Runtime>::InvalidEquivocationProof.into())),
+		);
+		assert_eq!(ValidatorSet::validators().len(), 2);
+		finalize_block();
+	});
+}
+
+#[test]
+fn aura_key_ownership_is_only_proven_for_validators() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+		assert!(Runtime::generate_aura_key_ownership_proof(
+			Sr25519Keyring::Charlie.public().into(),
+		).is_none());
+		finalize_block();
+	});
+}
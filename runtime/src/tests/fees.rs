@@ -0,0 +1,87 @@
+//! The fee multiplier following block fullness, and the weight to fee conversion.
+
+use super::*;
+use frame_support::weights::{DispatchClass, WeightToFeePolynomial};
+use sp_runtime::traits::{Convert, One};
+
+type FeeMultiplierUpdate = <Runtime as pallet_transaction_payment::Trait>::FeeMultiplierUpdate;
+
+// The weight available to normal extrinsics in a block.
+fn max_normal_weight() -> Weight {
+	AvailableBlockRatio::get() * MaximumBlockWeight::get()
+}
+
+// Run `f` in a block that has consumed `weight` of normal dispatch weight.
+fn with_block_weight(weight: Weight, f: impl FnOnce()) {
+	new_test_ext().execute_with(|| {
+		System::register_extra_weight_unchecked(weight, DispatchClass::Normal);
+		f();
+	});
+}
+
+// The multipliers that `FeeMultiplierUpdate` yields for `blocks` blocks like the current one.
+fn multipliers(start: Multiplier, blocks: usize) -> Vec<Multiplier> {
+	let mut multipliers = vec![start];
+	for _ in 0..blocks {
+		let next = FeeMultiplierUpdate::convert(*multipliers.last().unwrap());
+		multipliers.push(next);
+	}
+	multipliers
+}
+
+#[test]
+fn multiplier_rises_while_blocks_are_full() {
+	with_block_weight(max_normal_weight(), || {
+		let multipliers = multipliers(Multiplier::one(), 1_000);
+		assert!(multipliers.windows(2).all(|w| w[1] > w[0]));
+	});
+}
+
+#[test]
+fn multiplier_holds_at_target_fullness() {
+	with_block_weight(TargetBlockFullness::get() * max_normal_weight(), || {
+		let start = Multiplier::saturating_from_rational(3, 2);
+		assert!(multipliers(start, 1_000).into_iter().all(|m| m == start));
+	});
+}
+
+#[test]
+fn multiplier_falls_to_the_minimum_while_blocks_are_empty() {
+	with_block_weight(0, || {
+		let multipliers = multipliers(Multiplier::one(), 1_000);
+		assert!(multipliers.windows(2).all(|w| w[1] < w[0]));
+
+		// Once at the minimum it stays there.
+		let minimum = MinimumMultiplier::get();
+		assert_eq!(FeeMultiplierUpdate::convert(minimum), minimum);
+		let just_above = minimum.saturating_add(Multiplier::from_inner(1));
+		assert_eq!(FeeMultiplierUpdate::convert(just_above), minimum);
+	});
+}
+
+#[test]
+fn full_blocks_make_transactions_more_expensive() {
+	new_test_ext().execute_with(|| {
+		let transfer = Call::Balances(BalancesCall::transfer(
+			Sr25519Keyring::Bob.to_account_id().into(),
+			1_000,
+		));
+		let xt = sign(Sr25519Keyring::Alice, 0, transfer);
+		let fee_before = fee_of(&xt);
+
+		for number in 1..=10 {
+			initialize_block(number);
+			System::register_extra_weight_unchecked(max_normal_weight(), DispatchClass::Normal);
+			finalize_block();
+		}
+
+		assert!(TransactionPayment::next_fee_multiplier() > Multiplier::one());
+		assert!(fee_of(&xt) > fee_before);
+	});
+}
+
+#[test]
+fn base_extrinsic_weight_costs_a_tenth_of_a_cent() {
+	assert_eq!(impls::WeightToFee::calc(&ExtrinsicBaseWeight::get()), CENTS / 10);
+	assert_eq!(impls::WeightToFee::calc(&0), 0);
+}
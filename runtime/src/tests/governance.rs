@@ -0,0 +1,65 @@
+//! Root calls made through the council, the technical committee and referenda.
+
+use super::*;
+use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+use sp_runtime::DispatchError;
+
+#[test]
+fn genesis_seats_the_council_and_the_technical_committee() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		let mut council = vec![alice, bob];
+		council.sort();
+		assert_eq!(Council::members(), council);
+		assert_eq!(CouncilMembership::members(), council);
+		assert_eq!(TechnicalCommittee::members(), vec![charlie.clone()]);
+		assert_eq!(TechnicalMembership::members(), vec![charlie]);
+	});
+}
+
+#[test]
+fn the_council_alone_cannot_set_code() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+
+		let set_code = Call::System(frame_system::Call::set_code(vec![]));
+		assert_eq!(council_motion(set_code), Err(DispatchError::BadOrigin));
+	});
+}
+
+#[test]
+fn passed_referenda_are_dispatched_as_root() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		initialize_block(1);
+
+		let add = Call::ValidatorSet(pallet_validator_set::Call::add_validator(
+			charlie.clone().into(),
+		));
+		start_referendum(add);
+
+		run_to_block(3);
+		assert!(!ValidatorSet::validators().contains(&charlie));
+		run_to_block(4);
+		assert!(ValidatorSet::validators().contains(&charlie));
+	});
+}
+
+#[test]
+fn upgrades_remove_the_sudo_key() {
+	new_test_ext().execute_with(|| {
+		// The key a runtime with the sudo pallet stored.
+		let key = migrations::RemoveSudo::key();
+		unhashed::put(&key, &Sr25519Keyring::Alice.to_account_id());
+
+		let removed = migrations::RemoveSudo::on_runtime_upgrade();
+		assert!(!unhashed::exists(&key));
+
+		// Later upgrades find nothing to remove.
+		let unchanged = migrations::RemoveSudo::on_runtime_upgrade();
+		assert!(unchanged < removed);
+		assert!(!unhashed::exists(&key));
+	});
+}
@@ -0,0 +1,90 @@
+//! GRANDPA equivocation reports, proven against `Historical`, reported to `Offences` and handled
+//! by `ValidatorSet`.
+
+use super::*;
+use fg_primitives::runtime_decl_for_GrandpaApi::GrandpaApi;
+use sp_keyring::Ed25519Keyring;
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
+
+type EquivocationProof = fg_primitives::EquivocationProof<Hash, BlockNumber>;
+
+// A proof that `offender` prevoted for two different blocks in round 1 of `set_id`.
+fn prevote_equivocation(
+	set_id: fg_primitives::SetId,
+	offender: Ed25519Keyring,
+) -> EquivocationProof {
+	let round = 1;
+	let signed_prevote = |target_hash: Hash| {
+		let prevote = finality_grandpa::Prevote { target_hash, target_number: 1 };
+		let message = finality_grandpa::Message::Prevote(prevote.clone());
+		let payload = fg_primitives::localized_payload(round, set_id, &message);
+		let signature: fg_primitives::AuthoritySignature = offender.sign(&payload).into();
+		(prevote, signature)
+	};
+
+	EquivocationProof::new(
+		set_id,
+		fg_primitives::Equivocation::Prevote(finality_grandpa::Equivocation {
+			round_number: round,
+			identity: offender.public().into(),
+			first: signed_prevote(Hash::repeat_byte(1)),
+			second: signed_prevote(Hash::repeat_byte(2)),
+		}),
+	)
+}
+
+fn report(
+	equivocation_proof: EquivocationProof,
+	key_owner_proof: sp_session::MembershipProof,
+) -> UncheckedExtrinsic {
+	UncheckedExtrinsic::new_unsigned(Call::Grandpa(
+		pallet_grandpa::Call::report_equivocation_unsigned(equivocation_proof, key_owner_proof),
+	))
+}
+
+#[test]
+fn double_prevote_is_reported_as_an_offence() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+		let set_id = Grandpa::current_set_id();
+		let equivocation_proof = prevote_equivocation(set_id, Ed25519Keyring::Alice);
+		let key_owner_proof = Runtime::generate_key_ownership_proof(
+			set_id,
+			Ed25519Keyring::Alice.public().into(),
+		).and_then(|proof| proof.decode()).expect("Alice is a current authority");
+
+		let xt = report(equivocation_proof.clone(), key_owner_proof.clone());
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		assert!(events().iter().any(|event| matches!(
+			event,
+			Event::pallet_offences(pallet_offences::Event::Offence(..)),
+		)));
+
+		// Alice is removed from the set and leaves after the next session.
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		assert_eq!(ValidatorSet::validators(), vec![Sr25519Keyring::Bob.to_account_id()]);
+		assert!(events().contains(&Event::pallet_validator_set(
+			pallet_validator_set::RawEvent::ValidatorRemoved(alice),
+		)));
+
+		// The same offence cannot be reported twice.
+		let xt = report(equivocation_proof, key_owner_proof);
+		assert_eq!(
+			Executive::apply_extrinsic(xt),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)),
+		);
+		finalize_block();
+	});
+}
+
+#[test]
+fn key_ownership_is_only_proven_for_authorities() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+		assert!(Runtime::generate_key_ownership_proof(
+			Grandpa::current_set_id(),
+			Ed25519Keyring::Charlie.public().into(),
+		).is_none());
+		finalize_block();
+	});
+}
@@ -0,0 +1,116 @@
+//! Accounts named by `Address` as an id, an index from `Indices` or raw bytes.
+
+use super::*;
+use crate::address::MultiAddress;
+use codec::Decode;
+use frame_support::assert_ok;
+use sp_runtime::DispatchError;
+
+// Build `call` into an extrinsic signed by `signer` at `nonce` that names its signer by `address`.
+fn sign_as(
+	signer: Sr25519Keyring,
+	address: Address,
+	nonce: Index,
+	call: Call,
+) -> UncheckedExtrinsic {
+	let payload = SignedPayload::new(call, signed_extra(nonce, 0)).unwrap();
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+	let (call, extra, _) = payload.deconstruct();
+	UncheckedExtrinsic::new_signed(call, address, signature.into(), extra)
+}
+
+fn transfer_to(dest: Address) -> Call {
+	Call::Balances(BalancesCall::transfer(dest, 1_000))
+}
+
+#[test]
+fn transfers_reach_accounts_by_index() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		initialize_block(1);
+		assert_ok!(Indices::claim(Origin::signed(charlie.clone()), 7));
+		assert_eq!(Indices::lookup_index(7), Some(charlie.clone()));
+
+		let xt = sign(Sr25519Keyring::Alice, 0, transfer_to(MultiAddress::Index(7)));
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+
+		assert_eq!(Balances::reserved_balance(&charlie), IndexDeposit::get());
+		assert_eq!(Balances::free_balance(&charlie), ENDOWMENT - IndexDeposit::get() + 1_000);
+		finalize_block();
+	});
+}
+
+#[test]
+fn unclaimed_indices_cannot_be_looked_up() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+
+		let xt = sign(Sr25519Keyring::Alice, 0, transfer_to(MultiAddress::Index(7)));
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Err(DispatchError::CannotLookup)));
+		finalize_block();
+	});
+}
+
+#[test]
+fn transfers_reach_accounts_by_raw_bytes() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		let raw = AsRef::<[u8]>::as_ref(&charlie).to_vec();
+		initialize_block(1);
+
+		let xt = sign(Sr25519Keyring::Alice, 0, transfer_to(MultiAddress::Raw(raw.clone())));
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		assert_eq!(Balances::free_balance(&charlie), ENDOWMENT + 1_000);
+
+		// Only the 32 bytes of an account id name an account.
+		let short = MultiAddress::Raw(raw[..31].to_vec());
+		let xt = sign(Sr25519Keyring::Alice, 1, transfer_to(short));
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Err(DispatchError::CannotLookup)));
+		finalize_block();
+	});
+}
+
+#[test]
+fn signers_can_be_named_by_index() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		initialize_block(1);
+		assert_ok!(Indices::claim(Origin::signed(alice.clone()), 0));
+
+		let transfer = transfer_to(charlie.clone().into());
+		let xt = sign_as(Sr25519Keyring::Alice, MultiAddress::Index(0), 0, transfer);
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		assert_eq!(System::account_nonce(&alice), 1);
+		assert_eq!(Balances::free_balance(&charlie), ENDOWMENT + 1_000);
+		finalize_block();
+	});
+}
+
+#[test]
+fn signers_encoded_as_bare_account_ids_are_not_read_as_the_same_signer() {
+	new_test_ext().execute_with(|| {
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		let xt = sign(Sr25519Keyring::Alice, 0, transfer_to(bob.into()));
+		let mut encoded: Vec<u8> = Decode::decode(&mut &xt.encode()[..]).unwrap();
+		// The `MultiAddress::Id` variant byte after the version byte.
+		assert_eq!(encoded.remove(1), 0);
+
+		// Account ids starting with each variant's byte, and with a byte that names no variant.
+		for first in 0..=3 {
+			let signer = AccountId::from([first; 32]);
+
+			// The signer encoded the way it was before `transaction_version` 4. Whether the rest
+			// decodes depends on the signature's bytes, but never to the same signer.
+			let mut old = encoded.clone();
+			old[1..33].copy_from_slice(&[first; 32]);
+			let decoded = UncheckedExtrinsic::decode(&mut &old.encode()[..]);
+			if first > 2 {
+				assert!(decoded.is_err());
+			} else if let Ok(xt) = decoded {
+				let (address, _, _) = xt.signature.expect("the version byte marks it signed");
+				assert_ne!(address, MultiAddress::Id(signer));
+			}
+		}
+	});
+}
@@ -0,0 +1,298 @@
+//! Tests that run the real `Runtime` through `Executive`, from genesis to finalized blocks.
+
+use crate::*;
+use codec::Encode;
+use frame_support::{assert_ok, weights::GetDispatchInfo, StorageMap};
+use pallet_democracy::{AccountVote, Conviction, Vote};
+use sp_core::Pair;
+use sp_keyring::Sr25519Keyring;
+use sp_runtime::{
+	generic::{Era, SignedPayload},
+	traits::{Hash as HashT, Header as HeaderT},
+};
+
+mod assets;
+mod aura;
+mod fees;
+mod governance;
+mod grandpa;
+mod indices;
+mod multisig;
+mod proxy;
+mod scheduler;
+mod session;
+mod template;
+mod treasury;
+mod utility;
+mod vesting;
+
+/// The balance every endowed account starts with, as in the node's `testnet_genesis`.
+pub const ENDOWMENT: Balance = 1 << 60;
+
+/// Storage built the way the node's `testnet_genesis` builds it for a local testnet: Alice and
+/// Bob are authorities and the council, and Alice, Bob and Charlie are endowed. Unlike the
+/// node's, the technical committee is Charlie alone.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with_vesting(vec![])
+}
+
+/// Storage built like `new_test_ext`'s, with the given `(account, begin, length, liquid)` vesting
+/// schedules for endowed accounts.
+pub fn new_test_ext_with_vesting(
+	vesting: Vec<(AccountId, BlockNumber, BlockNumber, Balance)>,
+) -> sp_io::TestExternalities {
+	let authorities = [Sr25519Keyring::Alice, Sr25519Keyring::Bob];
+	let endowed = [Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie];
+
+	let storage = GenesisConfig {
+		frame_system: Some(SystemConfig {
+			code: vec![],
+			changes_trie_config: Default::default(),
+		}),
+		pallet_balances: Some(BalancesConfig {
+			balances: endowed.iter().map(|k| (k.to_account_id(), ENDOWMENT)).collect(),
+		}),
+		pallet_validator_set: Some(ValidatorSetConfig {
+			validators: authorities.iter().map(|k| k.to_account_id()).collect(),
+		}),
+		pallet_session: Some(SessionConfig {
+			keys: authorities.iter()
+				.map(|k| (k.to_account_id(), k.to_account_id(), session_keys(*k)))
+				.collect(),
+		}),
+		pallet_aura: Some(AuraConfig {
+			authorities: vec![],
+		}),
+		pallet_grandpa: Some(GrandpaConfig {
+			authorities: vec![],
+		}),
+		pallet_template: Some(Default::default()),
+		pallet_treasury: Some(Default::default()),
+		pallet_collective_Instance1: Some(Default::default()),
+		pallet_membership_Instance1: Some(CouncilMembershipConfig {
+			members: authorities.iter().map(|k| k.to_account_id()).collect(),
+			phantom: Default::default(),
+		}),
+		pallet_collective_Instance2: Some(Default::default()),
+		pallet_membership_Instance2: Some(TechnicalMembershipConfig {
+			members: vec![Sr25519Keyring::Charlie.to_account_id()],
+			phantom: Default::default(),
+		}),
+		pallet_democracy: Some(Default::default()),
+		pallet_indices: Some(IndicesConfig {
+			indices: vec![],
+		}),
+		pallet_vesting: Some(VestingConfig {
+			vesting,
+		}),
+	}.build_storage().unwrap();
+
+	sp_io::TestExternalities::new(storage)
+}
+
+/// The session keys the node's `authority_keys_from_seed` derives for `keyring`'s seed.
+pub fn session_keys(keyring: Sr25519Keyring) -> opaque::SessionKeys {
+	let grandpa = sp_core::ed25519::Pair::from_string(&keyring.to_seed(), None)
+		.expect("keyring seeds are valid; qed");
+	opaque::SessionKeys {
+		aura: AuraId::from(keyring.public()),
+		grandpa: GrandpaId::from(grandpa.public()),
+	}
+}
+
+/// Initialize block `number` on top of the current one and apply its timestamp inherent.
+///
+/// The block is authored in slot `number`, as Aura would author it at its timestamp, so its author
+/// is the authority at `number % authorities`.
+pub fn initialize_block(number: BlockNumber) {
+	let slot = u64::from(number);
+	initialize_block_with_digest(number, generic::Digest {
+		logs: vec![DigestItem::PreRuntime(sp_consensus_aura::AURA_ENGINE_ID, slot.encode())],
+	});
+}
+
+/// Initialize block `number` with `digest` on top of the current one and apply its timestamp
+/// inherent. Without an Aura pre-runtime digest, as when sealed by `--sealing instant` or
+/// `manual`, the block has no author.
+pub fn initialize_block_with_digest(number: BlockNumber, digest: generic::Digest<Hash>) {
+	let parent_hash = System::block_hash(number - 1);
+	Executive::initialize_block(&Header::new(
+		number,
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		digest,
+	));
+
+	let now = u64::from(number) * SLOT_DURATION;
+	let inherent = UncheckedExtrinsic::new_unsigned(Call::Timestamp(TimestampCall::set(now)));
+	assert_eq!(Executive::apply_extrinsic(inherent), Ok(Ok(())));
+}
+
+/// Finalize the current block and record its hash so the next block can build on it.
+pub fn finalize_block() -> Header {
+	let header = Executive::finalize_block();
+	// `finalize_block` only knows the parent hash; store this block's hash the way the client
+	// would by initializing its child.
+	frame_system::BlockHash::<Runtime>::insert(*header.number(), header.hash());
+	header
+}
+
+/// Run empty blocks until `number` has been finalized.
+pub fn run_to_block(number: BlockNumber) {
+	while System::block_number() < number {
+		let next = System::block_number() + 1;
+		initialize_block(next);
+		finalize_block();
+	}
+}
+
+/// The signed extensions a wallet would attach to a transaction from an account at `nonce`.
+pub fn signed_extra(nonce: Index, tip: Balance) -> SignedExtra {
+	(
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(nonce),
+		frame_system::CheckWeight::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::from(tip),
+	)
+}
+
+/// Build `call` into an extrinsic signed by `signer` at `nonce`.
+pub fn sign(signer: Sr25519Keyring, nonce: Index, call: Call) -> UncheckedExtrinsic {
+	let payload = SignedPayload::new(call, signed_extra(nonce, 0)).unwrap();
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+	let (call, extra, _) = payload.deconstruct();
+	UncheckedExtrinsic::new_signed(call, signer.to_account_id().into(), signature.into(), extra)
+}
+
+/// The fee `ChargeTransactionPayment` takes for `xt`, which must not declare a post-dispatch
+/// weight.
+pub fn fee_of(xt: &UncheckedExtrinsic) -> Balance {
+	TransactionPayment::compute_fee(xt.encode().len() as u32, &xt.function.get_dispatch_info(), 0)
+}
+
+/// The events deposited in the current block, without their phase and topics.
+pub fn events() -> Vec<Event> {
+	System::events().into_iter().map(|record| record.event).collect()
+}
+
+/// Propose `call` to the council from Alice, have Bob vote for it and close the motion, which
+/// dispatches `call` with the origin of the whole council.
+pub fn council_motion(call: Call) -> sp_runtime::DispatchResult {
+	let alice = Sr25519Keyring::Alice.to_account_id();
+	let bob = Sr25519Keyring::Bob.to_account_id();
+	let length = call.encode().len() as u32;
+	let weight = call.get_dispatch_info().weight;
+	let hash = BlakeTwo256::hash_of(&call);
+	let index = Council::proposal_count();
+
+	assert_ok!(Council::propose(Origin::signed(alice), 2, Box::new(call), length));
+	assert_ok!(Council::vote(Origin::signed(bob.clone()), hash, index, true));
+	assert_ok!(Council::close(Origin::signed(bob), hash, index, weight, length));
+
+	events().into_iter().rev().find_map(|event| match event {
+		Event::pallet_collective_Instance1(pallet_collective::RawEvent::Executed(h, result))
+			if h == hash => Some(result),
+		_ => None,
+	}).expect("the motion was closed with enough votes to execute it")
+}
+
+/// Have the council table `call` and the technical committee, Charlie alone, make it a referendum
+/// that ends at block 3 and is enacted a block later, and vote for it with Alice. Finalizes the
+/// current block, which must be block 1.
+pub fn start_referendum(call: Call) {
+	let alice = Sr25519Keyring::Alice.to_account_id();
+	let charlie = Sr25519Keyring::Charlie.to_account_id();
+	let hash = BlakeTwo256::hash_of(&call);
+	assert_ok!(Democracy::note_preimage(Origin::signed(alice.clone()), call.encode()));
+
+	let external = Call::Democracy(pallet_democracy::Call::external_propose_majority(hash));
+	assert_eq!(council_motion(external), Ok(()));
+	let fast_track = Call::Democracy(pallet_democracy::Call::fast_track(hash, 2, 1));
+	let length = fast_track.encode().len() as u32;
+	assert_ok!(TechnicalCommittee::propose(
+		Origin::signed(charlie),
+		1,
+		Box::new(fast_track),
+		length,
+	));
+
+	let aye = AccountVote::Standard {
+		vote: Vote { aye: true, conviction: Conviction::None },
+		balance: UNITS,
+	};
+	let index = Democracy::referendum_count() - 1;
+	assert_ok!(Democracy::vote(Origin::signed(alice), index, aye));
+	finalize_block();
+}
+
+#[test]
+fn genesis_matches_testnet_genesis() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		assert_eq!(Balances::free_balance(&alice), ENDOWMENT);
+		assert_eq!(Balances::free_balance(&Sr25519Keyring::Dave.to_account_id()), 0);
+		assert_eq!(Aura::authorities().len(), 2);
+		assert_eq!(Grandpa::grandpa_authorities().len(), 2);
+	});
+}
+
+#[test]
+fn empty_blocks_are_produced() {
+	new_test_ext().execute_with(|| {
+		run_to_block(3);
+		assert_eq!(System::block_number(), 3);
+		assert_eq!(Timestamp::now(), 3 * SLOT_DURATION);
+	});
+}
+
+#[test]
+fn transfers_charge_fees_and_bump_nonces() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		initialize_block(1);
+
+		let transfer = Call::Balances(BalancesCall::transfer(bob.clone().into(), 1_000));
+		let xt = sign(Sr25519Keyring::Alice, 0, transfer);
+		let fee = fee_of(&xt);
+		assert!(fee > 0);
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+
+		assert_eq!(Balances::free_balance(&alice), ENDOWMENT - 1_000 - fee);
+		// Bob authors block 1 and receives a share of the fee.
+		assert_eq!(Balances::free_balance(&bob), ENDOWMENT + 1_000 + fee - fee * 80 / 100);
+		assert_eq!(System::account_nonce(&alice), 1);
+		// Fees are moved to the treasury and the author rather than burned.
+		assert_eq!(Balances::total_issuance(), 3 * ENDOWMENT + ExistentialDeposit::get());
+		finalize_block();
+	});
+}
+
+#[test]
+fn stale_and_future_nonces_are_rejected() {
+	use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
+
+	new_test_ext().execute_with(|| {
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		initialize_block(1);
+
+		let transfer = Call::Balances(BalancesCall::transfer(bob.into(), 1_000));
+		assert_eq!(
+			Executive::apply_extrinsic(sign(Sr25519Keyring::Alice, 1, transfer.clone())),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Future)),
+		);
+		assert_eq!(
+			Executive::apply_extrinsic(sign(Sr25519Keyring::Alice, 0, transfer.clone())),
+			Ok(Ok(())),
+		);
+		assert_eq!(
+			Executive::apply_extrinsic(sign(Sr25519Keyring::Alice, 0, transfer)),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)),
+		);
+		finalize_block();
+	});
+}
@@ -0,0 +1,56 @@
+//! Accounts controlled jointly through `Multisig`.
+
+use super::*;
+use frame_support::{assert_ok, traits::Currency};
+
+#[test]
+fn two_of_three_signatories_dispatch_from_a_multisig_account() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		let mut signatories = vec![alice.clone(), bob.clone(), charlie];
+		signatories.sort();
+		let others = |who: &AccountId| {
+			signatories.iter().filter(|s| *s != who).cloned().collect::<Vec<_>>()
+		};
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		let multisig = Multisig::multi_account_id(&signatories, 2);
+		let _ = Balances::make_free_balance_be(&multisig, 100 * UNITS);
+		initialize_block(1);
+
+		let call = Call::Balances(BalancesCall::transfer(dave.clone().into(), 10 * UNITS));
+		let weight = call.get_dispatch_info().weight;
+
+		// The first approval records the operation and reserves its deposit.
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(alice.clone()),
+			2,
+			others(&alice),
+			None,
+			call.encode(),
+			false,
+			0,
+		));
+		let timepoint = Multisig::timepoint();
+		assert_eq!(Balances::free_balance(&dave), 0);
+		assert_eq!(
+			Balances::reserved_balance(&alice),
+			MultisigDepositBase::get() + 2 * MultisigDepositFactor::get(),
+		);
+
+		// The second dispatches it and returns the deposit.
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(bob.clone()),
+			2,
+			others(&bob),
+			Some(timepoint),
+			call.encode(),
+			false,
+			weight,
+		));
+		assert_eq!(Balances::free_balance(&dave), 10 * UNITS);
+		assert_eq!(Balances::free_balance(&multisig), 90 * UNITS);
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+	});
+}
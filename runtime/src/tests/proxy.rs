@@ -0,0 +1,173 @@
+//! Calls made by `Proxy` accounts on behalf of Alice, filtered by their `ProxyType`.
+
+use super::*;
+use frame_support::assert_ok;
+use sp_runtime::{DispatchError, DispatchResult};
+
+// Make Bob a proxy of `proxy_type` for Alice.
+fn add_bob_as(proxy_type: ProxyType) {
+	let alice = Sr25519Keyring::Alice.to_account_id();
+	let bob = Sr25519Keyring::Bob.to_account_id();
+	assert_ok!(Proxy::add_proxy(Origin::signed(alice), bob, proxy_type, 0));
+}
+
+// Have Bob dispatch `call` for Alice, and return the result the proxy pallet reports for it.
+fn proxy_for_alice(call: Call) -> DispatchResult {
+	let alice = Sr25519Keyring::Alice.to_account_id();
+	let bob = Sr25519Keyring::Bob.to_account_id();
+	assert_ok!(Proxy::proxy(Origin::signed(bob), alice, None, Box::new(call)));
+
+	events().into_iter().rev().find_map(|event| match event {
+		Event::pallet_proxy(pallet_proxy::RawEvent::ProxyExecuted(result)) => Some(result),
+		_ => None,
+	}).expect("`proxy` reports the result of the call it dispatched")
+}
+
+fn transfer() -> Call {
+	Call::Balances(BalancesCall::transfer(Sr25519Keyring::Charlie.to_account_id().into(), 1_000))
+}
+
+fn do_something() -> Call {
+	Call::TemplateModule(pallet_template::Call::do_something(42))
+}
+
+fn council_proposal() -> Call {
+	let remark = Call::System(frame_system::Call::remark(vec![]));
+	let length = remark.encode().len() as u32;
+	Call::Council(pallet_collective::Call::propose(2, Box::new(remark), length))
+}
+
+#[test]
+fn any_proxies_make_any_call() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		initialize_block(1);
+		add_bob_as(ProxyType::Any);
+
+		assert_eq!(proxy_for_alice(transfer()), Ok(()));
+		assert_eq!(Balances::free_balance(&charlie), ENDOWMENT + 1_000);
+		assert_eq!(proxy_for_alice(do_something()), Ok(()));
+		assert_eq!(proxy_for_alice(council_proposal()), Ok(()));
+	});
+}
+
+#[test]
+fn non_transfer_proxies_cannot_move_balances() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		initialize_block(1);
+		add_bob_as(ProxyType::NonTransfer);
+
+		assert_eq!(proxy_for_alice(transfer()), Err(DispatchError::BadOrigin));
+		assert_ok!(Indices::claim(Origin::signed(alice), 7));
+		let index_transfer = Call::Indices(pallet_indices::Call::transfer(
+			Sr25519Keyring::Charlie.to_account_id(),
+			7,
+		));
+		assert_eq!(proxy_for_alice(index_transfer), Err(DispatchError::BadOrigin));
+
+		assert_eq!(proxy_for_alice(do_something()), Ok(()));
+		assert_eq!(proxy_for_alice(council_proposal()), Ok(()));
+	});
+}
+
+#[test]
+fn non_transfer_proxies_cannot_move_funds_through_other_pallets() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		initialize_block(1);
+		add_bob_as(ProxyType::NonTransfer);
+
+		// Multisig calls dispatch from multisig accounts Alice signs for, moving their funds.
+		let as_multi = Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(
+			vec![charlie.clone()],
+			Box::new(transfer()),
+		));
+		assert_eq!(proxy_for_alice(as_multi), Err(DispatchError::BadOrigin));
+
+		// Minting an asset Alice owns would create balances of it.
+		assert_ok!(Assets::create(Origin::signed(alice), 0, 1));
+		let mint = Call::Assets(pallet_assets::Call::mint(0, charlie.clone().into(), 1_000));
+		assert_eq!(proxy_for_alice(mint), Err(DispatchError::BadOrigin));
+		assert_eq!(Assets::balance(0, charlie.clone()), 0);
+
+		// Batches still filter each call in them.
+		let batch = Call::Utility(pallet_utility::Call::batch(vec![do_something(), transfer()]));
+		assert_eq!(proxy_for_alice(batch), Ok(()));
+		assert!(events().contains(&Event::pallet_utility(
+			pallet_utility::Event::BatchInterrupted(1, DispatchError::BadOrigin),
+		)));
+		assert_eq!(Balances::free_balance(&charlie), ENDOWMENT);
+	});
+}
+
+#[test]
+fn non_transfer_proxies_cannot_add_any_proxies() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		initialize_block(1);
+		add_bob_as(ProxyType::NonTransfer);
+
+		let add_any = Call::Proxy(pallet_proxy::Call::add_proxy(
+			charlie.clone(),
+			ProxyType::Any,
+			0,
+		));
+		assert_eq!(proxy_for_alice(add_any), Err(DispatchError::BadOrigin));
+		let add_template = Call::Proxy(pallet_proxy::Call::add_proxy(
+			charlie,
+			ProxyType::TemplateOnly,
+			0,
+		));
+		assert_eq!(proxy_for_alice(add_template), Ok(()));
+	});
+}
+
+#[test]
+fn governance_proxies_only_make_governance_calls() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+		add_bob_as(ProxyType::Governance);
+
+		assert_eq!(proxy_for_alice(council_proposal()), Ok(()));
+		assert_eq!(Council::proposals().len(), 1);
+		assert_eq!(proxy_for_alice(transfer()), Err(DispatchError::BadOrigin));
+		assert_eq!(proxy_for_alice(do_something()), Err(DispatchError::BadOrigin));
+	});
+}
+
+#[test]
+fn template_only_proxies_only_call_the_template_pallet() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		initialize_block(1);
+		add_bob_as(ProxyType::TemplateOnly);
+
+		assert_eq!(proxy_for_alice(do_something()), Ok(()));
+		assert_eq!(TemplateModule::something_of(&alice), Some(42));
+		assert_eq!(proxy_for_alice(transfer()), Err(DispatchError::BadOrigin));
+		assert_eq!(proxy_for_alice(council_proposal()), Err(DispatchError::BadOrigin));
+
+		// Batches are allowed, but each call in them is still filtered.
+		let batch = Call::Utility(pallet_utility::Call::batch(vec![do_something(), transfer()]));
+		assert_eq!(proxy_for_alice(batch), Ok(()));
+		assert!(events().contains(&Event::pallet_utility(
+			pallet_utility::Event::BatchInterrupted(1, DispatchError::BadOrigin),
+		)));
+	});
+}
+
+#[test]
+fn accounts_without_proxies_cannot_be_proxied() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		initialize_block(1);
+
+		assert_eq!(
+			Proxy::proxy(Origin::signed(bob), alice, None, Box::new(transfer())),
+			Err(pallet_proxy::Error::<Runtime>::NotProxy.into()),
+		);
+	});
+}
@@ -0,0 +1,134 @@
+//! Calls dispatched by `Scheduler` at later blocks.
+
+use super::*;
+use frame_support::assert_ok;
+use sp_core::storage::well_known_keys;
+use sp_runtime::DispatchError;
+
+// Whether `Scheduler` dispatched a call successfully in the current block.
+fn dispatched() -> bool {
+	events().into_iter().any(|event| matches!(
+		event,
+		Event::pallet_scheduler(pallet_scheduler::RawEvent::Dispatched(_, _, Ok(()))),
+	))
+}
+
+fn force_set_something(who: Sr25519Keyring, something: u64) -> Box<Call> {
+	Box::new(Call::TemplateModule(pallet_template::Call::force_set_something(
+		who.to_account_id().into(),
+		something,
+	)))
+}
+
+#[test]
+fn a_referendum_schedules_a_runtime_upgrade() {
+	new_test_ext().execute_with(|| {
+		let code = vec![1, 2, 3];
+		initialize_block(1);
+
+		// `set_code` itself would reject this code, which is no runtime.
+		let set_code = Call::System(frame_system::Call::set_code_without_checks(code.clone()));
+		let schedule = pallet_scheduler::Call::schedule(6, None, 0, Box::new(set_code));
+		start_referendum(Call::Scheduler(schedule));
+
+		// The referendum is enacted at block 4 and schedules the upgrade.
+		run_to_block(5);
+		assert_eq!(sp_io::storage::get(well_known_keys::CODE), Some(vec![]));
+		run_to_block(6);
+		assert!(dispatched());
+		assert_eq!(sp_io::storage::get(well_known_keys::CODE), Some(code));
+	});
+}
+
+#[test]
+fn only_root_schedules_calls() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		initialize_block(1);
+
+		assert_eq!(
+			Scheduler::schedule(
+				Origin::signed(alice),
+				5,
+				None,
+				0,
+				force_set_something(Sr25519Keyring::Dave, 7),
+			),
+			Err(DispatchError::BadOrigin),
+		);
+	});
+}
+
+#[test]
+fn template_calls_run_at_the_scheduled_block() {
+	new_test_ext().execute_with(|| {
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		initialize_block(1);
+		assert_ok!(Scheduler::schedule(
+			Origin::root(),
+			3,
+			None,
+			0,
+			force_set_something(Sr25519Keyring::Dave, 7),
+		));
+		finalize_block();
+
+		run_to_block(2);
+		assert_eq!(TemplateModule::something_of(&dave), None);
+		run_to_block(3);
+		assert_eq!(TemplateModule::something_of(&dave), Some(7));
+	});
+}
+
+#[test]
+fn named_calls_can_be_cancelled() {
+	new_test_ext().execute_with(|| {
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		let id = b"set-dave".to_vec();
+		initialize_block(1);
+		assert_ok!(Scheduler::schedule_named(
+			Origin::root(),
+			id.clone(),
+			3,
+			None,
+			0,
+			force_set_something(Sr25519Keyring::Dave, 7),
+		));
+		// Names are unique among scheduled calls.
+		assert!(Scheduler::schedule_named(
+			Origin::root(),
+			id.clone(),
+			4,
+			None,
+			0,
+			force_set_something(Sr25519Keyring::Dave, 8),
+		).is_err());
+		assert_ok!(Scheduler::cancel_named(Origin::root(), id));
+		finalize_block();
+
+		run_to_block(4);
+		assert_eq!(TemplateModule::something_of(&dave), None);
+	});
+}
+
+#[test]
+fn periodic_calls_repeat_the_given_number_of_times() {
+	new_test_ext().execute_with(|| {
+		initialize_block(1);
+		// Every 2 blocks from block 2, 3 times in all.
+		assert_ok!(Scheduler::schedule(
+			Origin::root(),
+			2,
+			Some((2, 3)),
+			0,
+			force_set_something(Sr25519Keyring::Dave, 7),
+		));
+		finalize_block();
+
+		let runs: Vec<_> = (2..=8).filter(|block| {
+			run_to_block(*block);
+			dispatched()
+		}).collect();
+		assert_eq!(runs, vec![2, 4, 6]);
+	});
+}
@@ -0,0 +1,99 @@
+//! Validator set changes rotated in by `Session`.
+
+use super::*;
+
+#[test]
+fn genesis_authorities_come_from_the_validator_set() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		assert_eq!(ValidatorSet::validators(), vec![alice.clone(), bob.clone()]);
+		assert_eq!(Session::validators(), vec![alice, bob]);
+		assert_eq!(Aura::authorities(), vec![
+			session_keys(Sr25519Keyring::Alice).aura,
+			session_keys(Sr25519Keyring::Bob).aura,
+		]);
+	});
+}
+
+#[test]
+fn added_validator_becomes_an_authority_after_two_rotations() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		let keys = session_keys(Sr25519Keyring::Charlie);
+
+		initialize_block(1);
+		let add = pallet_validator_set::Call::add_validator(charlie.clone().into());
+		assert_eq!(council_motion(Call::ValidatorSet(add)), Ok(()));
+		assert!(events().contains(&Event::pallet_validator_set(
+			pallet_validator_set::RawEvent::ValidatorAdded(charlie.clone()),
+		)));
+		let set_keys = sign(Sr25519Keyring::Charlie, 0, Call::Session(
+			pallet_session::Call::set_keys(keys.clone(), vec![]),
+		));
+		assert_eq!(Executive::apply_extrinsic(set_keys), Ok(Ok(())));
+		finalize_block();
+
+		// The first rotation only queues Charlie.
+		run_to_block(SessionPeriod::get());
+		assert_eq!(Session::current_index(), 1);
+		assert_eq!(Aura::authorities().len(), 2);
+
+		run_to_block(2 * SessionPeriod::get());
+		assert_eq!(Session::current_index(), 2);
+		assert!(Session::validators().contains(&charlie));
+		assert!(Aura::authorities().contains(&keys.aura));
+		assert!(Grandpa::grandpa_authorities().contains(&(keys.grandpa, 1)));
+	});
+}
+
+#[test]
+fn removed_validator_stops_being_an_authority_after_two_rotations() {
+	new_test_ext().execute_with(|| {
+		let bob = Sr25519Keyring::Bob.to_account_id();
+
+		initialize_block(1);
+		let remove = pallet_validator_set::Call::remove_validator(bob.clone().into());
+		assert_eq!(council_motion(Call::ValidatorSet(remove)), Ok(()));
+		finalize_block();
+
+		run_to_block(2 * SessionPeriod::get());
+		assert!(!Session::validators().contains(&bob));
+		assert_eq!(Aura::authorities(), vec![session_keys(Sr25519Keyring::Alice).aura]);
+		assert_eq!(Grandpa::grandpa_authorities().len(), 1);
+	});
+}
+
+#[test]
+fn only_root_or_two_thirds_of_the_council_change_the_validator_set() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		let add = Call::ValidatorSet(pallet_validator_set::Call::add_validator(
+			charlie.clone().into(),
+		));
+		initialize_block(1);
+
+		// An account outside the council.
+		let xt = sign(Sr25519Keyring::Charlie, 0, add.clone());
+		assert_eq!(
+			Executive::apply_extrinsic(xt),
+			Ok(Err(sp_runtime::DispatchError::BadOrigin)),
+		);
+
+		// A single council member: a threshold of one dispatches at once, with the origin of one
+		// member out of two.
+		let hash = BlakeTwo256::hash_of(&add);
+		let length = add.encode().len() as u32;
+		assert_ok!(Council::propose(Origin::signed(alice), 1, Box::new(add.clone()), length));
+		assert!(events().contains(&Event::pallet_collective_Instance1(
+			pallet_collective::RawEvent::Executed(hash, Err(sp_runtime::DispatchError::BadOrigin)),
+		)));
+		assert!(!ValidatorSet::validators().contains(&charlie));
+
+		// Both members, two thirds of the council or more.
+		assert_eq!(council_motion(add), Ok(()));
+		assert!(ValidatorSet::validators().contains(&charlie));
+		finalize_block();
+	});
+}
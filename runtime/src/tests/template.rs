@@ -0,0 +1,122 @@
+//! `TemplateModule` dispatched through signed extrinsics.
+
+use super::*;
+use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+use sp_io::hashing::twox_128;
+
+#[test]
+fn do_something_charges_fee_and_deposit() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		initialize_block(1);
+
+		let xt = sign(Sr25519Keyring::Alice, 0, Call::TemplateModule(
+			pallet_template::Call::do_something(42),
+		));
+		let fee = fee_of(&xt);
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+
+		assert_eq!(TemplateModule::something_of(&alice), Some(42));
+		assert_eq!(Balances::reserved_balance(&alice), StorageDeposit::get());
+		assert_eq!(Balances::free_balance(&alice), ENDOWMENT - fee - StorageDeposit::get());
+		assert_eq!(System::account_nonce(&alice), 1);
+		assert!(events().contains(&Event::pallet_template(
+			pallet_template::RawEvent::SomethingStored(42, alice),
+		)));
+		finalize_block();
+	});
+}
+
+#[test]
+fn failed_calls_still_pay_fees_and_bump_nonces() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		initialize_block(1);
+
+		let xt = sign(Sr25519Keyring::Alice, 0, Call::TemplateModule(
+			pallet_template::Call::cause_error(),
+		));
+		let fee = fee_of(&xt);
+		assert_eq!(
+			Executive::apply_extrinsic(xt),
+			Ok(Err(pallet_template::Error::<Runtime>::NoneValue.into())),
+		);
+
+		assert_eq!(Balances::free_balance(&alice), ENDOWMENT - fee);
+		assert_eq!(System::account_nonce(&alice), 1);
+		assert!(!events().iter().any(|event| matches!(event, Event::pallet_template(_))));
+		finalize_block();
+	});
+}
+
+#[test]
+fn values_and_history_persist_across_blocks() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+
+		initialize_block(1);
+		let xt = sign(Sr25519Keyring::Alice, 0, Call::TemplateModule(
+			pallet_template::Call::do_something(42),
+		));
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		finalize_block();
+
+		run_to_block(3);
+
+		initialize_block(4);
+		let xt = sign(Sr25519Keyring::Alice, 1, Call::TemplateModule(
+			pallet_template::Call::cause_error(),
+		));
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		finalize_block();
+
+		assert_eq!(TemplateModule::something_of(&alice), Some(43));
+		let blocks: Vec<_> = TemplateModule::history(2).into_iter()
+			.map(|entry| entry.block_number)
+			.collect();
+		assert_eq!(blocks, vec![1, 4]);
+	});
+}
+
+#[test]
+fn referenda_can_force_set_values() {
+	new_test_ext().execute_with(|| {
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		initialize_block(1);
+
+		let force_set = Call::TemplateModule(
+			pallet_template::Call::force_set_something(dave.clone().into(), 7),
+		);
+		start_referendum(force_set.clone());
+		run_to_block(4);
+		assert_eq!(TemplateModule::something_of(&dave), Some(7));
+		assert!(events().contains(&Event::pallet_template(
+			pallet_template::RawEvent::SomethingForceSet(7, dave),
+		)));
+
+		// Signed, the call is rejected with `BadOrigin`.
+		initialize_block(5);
+		let xt = sign(Sr25519Keyring::Bob, 0, force_set);
+		assert_eq!(
+			Executive::apply_extrinsic(xt),
+			Ok(Err(sp_runtime::DispatchError::BadOrigin)),
+		);
+		finalize_block();
+	});
+}
+
+#[test]
+fn upgrades_give_a_lone_global_value_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		// The layout of a chain from before values were kept per account: version `V1_0_0` and a
+		// global `u32` value.
+		let key = |item: &[u8]| [twox_128(b"TemplateModule"), twox_128(item)].concat();
+		unhashed::put(&key(b"StorageVersion"), &0u8);
+		unhashed::put(&key(b"Something"), &7u32);
+
+		migrations::TemplateMigrations::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::something(), Some(7));
+		assert_eq!(TemplateModule::something_of(Treasury::account_id()), Some(7));
+	});
+}
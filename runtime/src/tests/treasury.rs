@@ -0,0 +1,120 @@
+//! Fees and dust collected by `Treasury`, and spends approved by the council.
+
+use super::*;
+use frame_support::{assert_ok, traits::{Currency, OnInitialize}};
+use sp_runtime::DispatchError;
+
+// Build `call` into an extrinsic signed by `signer` at `nonce` that tips the block author.
+fn sign_with_tip(
+	signer: Sr25519Keyring,
+	nonce: Index,
+	tip: Balance,
+	call: Call,
+) -> UncheckedExtrinsic {
+	let payload = SignedPayload::new(call, signed_extra(nonce, tip)).unwrap();
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+	let (call, extra, _) = payload.deconstruct();
+	UncheckedExtrinsic::new_signed(call, signer.to_account_id().into(), signature.into(), extra)
+}
+
+fn treasury_balance() -> Balance {
+	Balances::free_balance(&Treasury::account_id())
+}
+
+#[test]
+fn genesis_creates_the_treasury_account() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(treasury_balance(), ExistentialDeposit::get());
+		assert_eq!(Treasury::pot(), 0);
+	});
+}
+
+#[test]
+fn fees_go_to_the_treasury_and_tips_to_the_author() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		// Slot 1 belongs to Bob, the second authority.
+		initialize_block(1);
+		assert_eq!(Authorship::author(), bob);
+
+		let tip = 1_000;
+		let transfer = Call::Balances(BalancesCall::transfer(charlie.into(), 1_000));
+		let xt = sign_with_tip(Sr25519Keyring::Alice, 0, tip, transfer);
+		let fee = fee_of(&xt);
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+
+		let to_treasury = fee * 80 / 100;
+		assert_eq!(Balances::free_balance(&alice), ENDOWMENT - 1_000 - fee - tip);
+		assert_eq!(treasury_balance(), ExistentialDeposit::get() + to_treasury);
+		assert_eq!(Balances::free_balance(&bob), ENDOWMENT + fee - to_treasury + tip);
+		finalize_block();
+	});
+}
+
+#[test]
+fn fees_and_tips_go_to_the_treasury_without_an_author() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		// Blocks sealed by manual seal carry no Aura pre-runtime digest.
+		initialize_block_with_digest(1, Default::default());
+
+		let tip = 1_000;
+		let transfer = Call::Balances(BalancesCall::transfer(charlie.into(), 1_000));
+		let xt = sign_with_tip(Sr25519Keyring::Alice, 0, tip, transfer);
+		let fee = fee_of(&xt);
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+
+		assert_eq!(Balances::free_balance(&alice), ENDOWMENT - 1_000 - fee - tip);
+		assert_eq!(treasury_balance(), ExistentialDeposit::get() + fee + tip);
+		assert_eq!(Balances::free_balance(&AccountId::default()), 0);
+		finalize_block();
+	});
+}
+
+#[test]
+fn dust_goes_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		let _ = Balances::make_free_balance_be(&dave, ExistentialDeposit::get() + 100);
+
+		// Leaves Dave below the existential deposit, so the rest of his balance is dust.
+		assert_ok!(Balances::transfer(Origin::signed(dave.clone()), alice.into(), 150));
+
+		assert_eq!(Balances::free_balance(&dave), 0);
+		assert_eq!(treasury_balance(), 2 * ExistentialDeposit::get() - 50);
+	});
+}
+
+#[test]
+fn spends_are_approved_by_the_council() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		let _ = Balances::make_free_balance_be(&Treasury::account_id(), 100 * UNITS);
+		initialize_block(1);
+
+		assert_ok!(Treasury::propose_spend(
+			Origin::signed(charlie.clone()),
+			10 * UNITS,
+			dave.clone().into(),
+		));
+		assert_eq!(
+			Treasury::approve_proposal(Origin::signed(charlie.clone()), 0),
+			Err(DispatchError::BadOrigin),
+		);
+
+		let approve = Call::Treasury(pallet_treasury::Call::approve_proposal(0));
+		assert_eq!(council_motion(approve), Ok(()));
+		assert_eq!(Treasury::approvals(), vec![0]);
+		finalize_block();
+
+		Treasury::on_initialize(SpendPeriod::get());
+		assert_eq!(Balances::free_balance(&dave), 10 * UNITS);
+		// The proposal bond is returned once the spend is paid.
+		assert_eq!(Balances::reserved_balance(&charlie), 0);
+	});
+}
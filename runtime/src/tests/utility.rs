@@ -0,0 +1,72 @@
+//! Batches and derivative accounts through `Utility`.
+
+use super::*;
+use frame_support::{assert_ok, traits::Currency};
+use sp_runtime::DispatchError;
+
+fn transfer(to: Sr25519Keyring, value: Balance) -> Call {
+	Call::Balances(BalancesCall::transfer(to.to_account_id().into(), value))
+}
+
+#[test]
+fn batches_dispatch_calls_until_one_fails() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		initialize_block(1);
+
+		let calls = vec![
+			transfer(Sr25519Keyring::Charlie, 1_000),
+			transfer(Sr25519Keyring::Charlie, ENDOWMENT),
+			transfer(Sr25519Keyring::Charlie, 2_000),
+		];
+		assert_ok!(Utility::batch(Origin::signed(alice.clone()), calls));
+
+		assert_eq!(Balances::free_balance(&charlie), ENDOWMENT + 1_000);
+		assert!(events().iter().any(|event| matches!(
+			event,
+			Event::pallet_utility(pallet_utility::Event::BatchInterrupted(1, _)),
+		)));
+	});
+}
+
+#[test]
+fn batch_all_reverts_every_call_if_one_fails() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		initialize_block(1);
+
+		let calls = vec![
+			transfer(Sr25519Keyring::Charlie, 1_000),
+			transfer(Sr25519Keyring::Charlie, ENDOWMENT),
+		];
+		let batch_all = Call::Utility(pallet_utility::Call::batch_all(calls));
+		let xt = sign(Sr25519Keyring::Alice, 0, batch_all);
+		// `batch_all` refunds the weight of the calls it did not reach.
+		let max_fee = fee_of(&xt);
+		assert!(matches!(Executive::apply_extrinsic(xt), Ok(Err(DispatchError::Module { .. }))));
+
+		// Only the fee is taken.
+		assert_eq!(Balances::free_balance(&charlie), ENDOWMENT);
+		assert!(Balances::free_balance(&alice) >= ENDOWMENT - max_fee);
+		finalize_block();
+	});
+}
+
+#[test]
+fn derivative_accounts_are_controlled_by_their_owner() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+		let derivative = Utility::derivative_account_id(alice.clone(), 0);
+		let _ = Balances::make_free_balance_be(&derivative, 10_000);
+		initialize_block(1);
+
+		let call = Box::new(transfer(Sr25519Keyring::Charlie, 1_000));
+		assert_ok!(Utility::as_derivative(Origin::signed(alice), 0, call));
+
+		assert_eq!(Balances::free_balance(&derivative), 9_000);
+		assert_eq!(Balances::free_balance(&charlie), ENDOWMENT + 1_000);
+	});
+}
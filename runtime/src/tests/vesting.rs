@@ -0,0 +1,120 @@
+//! Balances locked by `Vesting` schedules from genesis or from vested transfers.
+
+use super::*;
+use frame_support::assert_ok;
+use pallet_vesting::VestingInfo;
+
+/// Charlie's vesting schedule: all but 1_000 units of the endowment is liquid, and those unlock
+/// linearly over 100 blocks from block 10.
+const BEGIN: BlockNumber = 10;
+const LENGTH: BlockNumber = 100;
+const LOCKED: Balance = 1_000 * UNITS;
+const LIQUID: Balance = ENDOWMENT - LOCKED;
+const PER_BLOCK: Balance = LOCKED / LENGTH as Balance;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let charlie = Sr25519Keyring::Charlie.to_account_id();
+	new_test_ext_with_vesting(vec![(charlie, BEGIN, LENGTH, LIQUID)])
+}
+
+// The amount of `who`'s balance that is locked.
+fn locked(who: &AccountId) -> Balance {
+	Balances::locks(who).iter().map(|lock| lock.amount).max().unwrap_or(0)
+}
+
+#[test]
+fn genesis_locks_all_but_the_liquid_balance() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+
+		assert_eq!(Vesting::vesting(&charlie), Some(VestingInfo {
+			locked: LOCKED,
+			per_block: PER_BLOCK,
+			starting_block: BEGIN,
+		}));
+		assert_eq!(locked(&charlie), LOCKED);
+		assert_eq!(Vesting::vesting(&alice), None);
+		assert_eq!(locked(&alice), 0);
+
+		let too_much = Balances::transfer(
+			Origin::signed(charlie.clone()),
+			alice.clone().into(),
+			LIQUID + 1,
+		);
+		assert!(too_much.is_err());
+		assert_ok!(Balances::transfer(Origin::signed(charlie), alice.into(), LIQUID));
+	});
+}
+
+#[test]
+fn nothing_unlocks_before_the_schedule_begins() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+
+		run_to_block(BEGIN);
+		assert_eq!(Vesting::vesting_balance(&charlie), Some(LOCKED));
+		assert_ok!(Vesting::vest(Origin::signed(charlie.clone())));
+		assert_eq!(locked(&charlie), LOCKED);
+	});
+}
+
+#[test]
+fn the_balance_unlocks_linearly_once_the_schedule_begins() {
+	new_test_ext().execute_with(|| {
+		let charlie = Sr25519Keyring::Charlie.to_account_id();
+
+		run_to_block(BEGIN + 40);
+		assert_eq!(Vesting::vesting_balance(&charlie), Some(LOCKED - 40 * PER_BLOCK));
+		// The lock only follows the schedule when vested.
+		assert_eq!(locked(&charlie), LOCKED);
+		assert_ok!(Vesting::vest(Origin::signed(charlie.clone())));
+		assert_eq!(locked(&charlie), LOCKED - 40 * PER_BLOCK);
+
+		run_to_block(BEGIN + LENGTH);
+		assert_ok!(Vesting::vest(Origin::signed(charlie.clone())));
+		assert_eq!(locked(&charlie), 0);
+		assert_eq!(Vesting::vesting(&charlie), None);
+	});
+}
+
+#[test]
+fn vested_transfers_create_a_schedule() {
+	new_test_ext().execute_with(|| {
+		let dave = Sr25519Keyring::Dave.to_account_id();
+		initialize_block(1);
+
+		let schedule = VestingInfo { locked: 10 * UNITS, per_block: UNITS, starting_block: 5 };
+		let xt = sign(Sr25519Keyring::Alice, 0, Call::Vesting(
+			pallet_vesting::Call::vested_transfer(dave.clone().into(), schedule),
+		));
+		assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+		finalize_block();
+
+		assert_eq!(Balances::free_balance(&dave), 10 * UNITS);
+		assert_eq!(locked(&dave), 10 * UNITS);
+
+		run_to_block(8);
+		assert_eq!(Vesting::vesting_balance(&dave), Some(7 * UNITS));
+		assert_ok!(Vesting::vest(Origin::signed(dave.clone())));
+		assert_eq!(locked(&dave), 7 * UNITS);
+	});
+}
+
+#[test]
+fn vested_transfers_below_the_minimum_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let dave = Sr25519Keyring::Dave.to_account_id();
+
+		let schedule = VestingInfo {
+			locked: MinVestedTransfer::get() - 1,
+			per_block: 1,
+			starting_block: 0,
+		};
+		assert_eq!(
+			Vesting::vested_transfer(Origin::signed(alice), dave.into(), schedule),
+			Err(pallet_vesting::Error::<Runtime>::AmountLow.into()),
+		);
+	});
+}
@@ -0,0 +1,29 @@
+#!/usr/bin/env bash
+
+# Regenerates the weights of this repository's pallets from their benchmarks. Run it from the
+# repository root on the reference hardware the chain's weights are meant for.
+
+set -e
+
+echo "*** Building the node with benchmarks"
+
+cargo build --release --manifest-path node/Cargo.toml --features runtime-benchmarks
+
+benchmark() {
+    echo "*** Benchmarking $1"
+
+    ./target/release/node-template benchmark \
+        --chain=dev \
+        --steps=50 \
+        --repeat=20 \
+        --pallet="$1" \
+        --extrinsic='*' \
+        --execution=wasm \
+        --wasm-execution=compiled \
+        --heap-pages=4096 \
+        --output="$2"
+}
+
+benchmark pallet_template pallets/template/src/weights.rs
+benchmark pallet_validator_set pallets/validator-set/src/weights.rs
+benchmark pallet_assets pallets/assets/src/weights.rs